@@ -0,0 +1,43 @@
+use bevy_proc_anim::{
+    batch::DynamicsBatch,
+    state::{DynamicsParams, TickMode},
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn batch(mode: TickMode, springs: usize) -> DynamicsBatch {
+    let mut batch = DynamicsBatch::new(mode);
+    for i in 0..springs {
+        let params = DynamicsParams::new(1.0 + (i % 7) as f32, 0.5, 2.0);
+        batch.push(0.0, params);
+    }
+    for (i, target) in batch.targets_mut().iter_mut().enumerate() {
+        *target = (i % 13) as f32;
+    }
+    batch
+}
+
+fn tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_tick");
+    for mode in [TickMode::Stable, TickMode::PoleMatching, TickMode::Analytic] {
+        for springs in [1_000, 10_000, 100_000] {
+            let mut batch = batch(mode, springs);
+            group.throughput(Throughput::Elements(springs as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("{mode:?}"), springs),
+                &springs,
+                |b, _| b.iter(|| batch.tick(1.0 / 60.0)),
+            );
+            //frame times jitter in practice, so nothing depending on dt can be reused
+            let mut dts = [1.0 / 60.0, 1.0 / 59.0].into_iter().cycle();
+            group.bench_with_input(
+                BenchmarkId::new(format!("{mode:?}/varying_dt"), springs),
+                &springs,
+                |b, _| b.iter(|| batch.tick(dts.next().unwrap())),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick);
+criterion_main!(benches);
@@ -0,0 +1,139 @@
+use std::{hint::black_box, time::Duration};
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*, time::TimeUpdateStrategy};
+use bevy_proc_anim::{
+    props::{
+        ScaleXProperty, ScaleYProperty, ScaleZProperty, TranslationXProperty, TranslationYProperty,
+        TranslationZProperty,
+    },
+    state::{AnimHandle, DynamicsParams, DynamicsSet, DynamicsState, TickMode},
+    DynamicsCommandsExt, DynamicsEntityCommandsExt, DynamicsPlugin,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const DT: f32 = 1.0 / 60.0;
+
+// the dynamics run here instead of in the main schedule, so each bench runs only its own set
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Bench;
+
+#[derive(Resource)]
+struct BenchedSet(DynamicsSet);
+
+fn app(benched: DynamicsSet) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DynamicsPlugin {
+            schedule: Bench.intern(),
+            ..default()
+        },
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        DT,
+    )))
+    .insert_resource(BenchedSet(benched));
+    for set in [DynamicsSet::Read, DynamicsSet::Tick, DynamicsSet::Write] {
+        app.configure_sets(
+            Bench,
+            set.run_if(move |benched: Res<BenchedSet>| benched.0 == set),
+        );
+    }
+    //the first update has a zero delta, which would skip the dynamics
+    app.update();
+    app.update();
+    app
+}
+
+fn spawn_handles(app: &mut App, count: usize, mode: TickMode) -> Vec<AnimHandle<f32>> {
+    let world = app.world_mut();
+    let source = world.register_system(|time: Res<Time>| time.elapsed_secs().sin());
+    let params = DynamicsParams::new(2.0, 0.5, 2.0);
+
+    let mut commands = world.commands();
+    let handles = (0..count)
+        .map(|_| {
+            let handle = commands.animate_value(0.0, params, source);
+            commands.set_tick_mode(handle, mode);
+            handle
+        })
+        .collect();
+    world.flush();
+    handles
+}
+
+fn tick(c: &mut Criterion) {
+    const STEPS: usize = 1_000;
+
+    let mut group = c.benchmark_group("tick");
+    group.throughput(Throughput::Elements(STEPS as u64));
+    for mode in [
+        TickMode::Simple,
+        TickMode::Stable,
+        TickMode::PoleMatching,
+        TickMode::Analytic,
+    ] {
+        let mut state = DynamicsState::new(0.0, DynamicsParams::new(2.0, 0.5, 2.0));
+        group.bench_function(format!("{mode:?}"), |b| {
+            b.iter(|| {
+                for i in 0..STEPS {
+                    state.target = (i as f32 * DT).sin();
+                    state.tick(DT, None, mode);
+                }
+                black_box(state.value())
+            })
+        });
+    }
+    group.finish();
+}
+
+fn update_sources(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_sources");
+    for handles in [100, 1_000, 10_000] {
+        let mut app = app(DynamicsSet::Read);
+        spawn_handles(&mut app, handles, TickMode::Stable);
+
+        group.throughput(Throughput::Elements(handles as u64));
+        group.bench_function(BenchmarkId::from_parameter(handles), |b| {
+            b.iter(|| app.world_mut().run_schedule(Bench))
+        });
+    }
+    group.finish();
+}
+
+fn apply_dynamics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply_dynamics");
+    for entities in [100, 1_000, 10_000] {
+        for properties in [1, 3, 6] {
+            let mut app = app(DynamicsSet::Write);
+            let handles = spawn_handles(&mut app, properties, TickMode::Stable);
+
+            let world = app.world_mut();
+            let mut commands = world.commands();
+            for _ in 0..entities {
+                let mut entity = commands.spawn(Transform::default());
+                for (i, &handle) in handles.iter().enumerate() {
+                    match i {
+                        0 => entity.animate::<TranslationXProperty>(handle),
+                        1 => entity.animate::<TranslationYProperty>(handle),
+                        2 => entity.animate::<TranslationZProperty>(handle),
+                        3 => entity.animate::<ScaleXProperty>(handle),
+                        4 => entity.animate::<ScaleYProperty>(handle),
+                        _ => entity.animate::<ScaleZProperty>(handle),
+                    };
+                }
+            }
+            world.flush();
+
+            group.throughput(Throughput::Elements((entities * properties) as u64));
+            group.bench_function(
+                BenchmarkId::new(format!("{properties}_properties"), entities),
+                |b| b.iter(|| app.world_mut().run_schedule(Bench)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick, update_sources, apply_dynamics);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy_proc_anim::{
-    prop, state::DynamicsParams, DynamicsCommandsExt, DynamicsEntityCommandsExt, DynamicsPlugin,
+    props::TranslationYProperty, state::DynamicsParams, DynamicsCommandsExt,
+    DynamicsEntityCommandsExt, DynamicsPlugin,
 };
 
 fn main() {
@@ -55,13 +56,6 @@ fn setup_camera(mut commands: Commands) {
     commands.spawn(Camera2d);
 }
 
-prop!(
-    pub struct TranslationYProperty,
-    Transform,
-    f32,
-    |tf| &mut tf.translation.y
-);
-
 #[derive(Reflect)]
 struct SpriteSaturationProperty;
 
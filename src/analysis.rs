@@ -0,0 +1,152 @@
+use crate::state::{DynamicsParams, DynamicsState, TickMode};
+
+/// A response of a [`DynamicsParams`] to a test input, sampled every `dt` seconds starting
+/// at `t = 0`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Response {
+    pub dt: f32,
+    pub input: Vec<f32>,
+    pub output: Vec<f32>,
+}
+
+impl Response {
+    /// The time of each sample.
+    pub fn times(&self) -> impl Iterator<Item = f32> + '_ {
+        (0..self.output.len()).map(|i| i as f32 * self.dt)
+    }
+
+    /// Metrics of a step response, relative to the final value of the input.
+    pub fn metrics(&self) -> ResponseMetrics {
+        self.metrics_with_tolerance(0.02)
+    }
+
+    /// Like [`metrics`](Response::metrics), but settling within `tolerance` of the final
+    /// input, as a fraction of it. Without a step, e.g. for an impulse response, `tolerance`
+    /// is in the units of the output instead.
+    pub fn metrics_with_tolerance(&self, tolerance: f32) -> ResponseMetrics {
+        let initial = self.output.first().copied().unwrap_or_default();
+        let target = self.input.last().copied().unwrap_or_default();
+        let last = self.output.last().copied().unwrap_or_default();
+        let amplitude = target - initial;
+
+        let band = if amplitude == 0.0 {
+            tolerance
+        } else {
+            (tolerance * amplitude).abs()
+        };
+        let settling_time = match self.output.iter().rposition(|&y| (y - target).abs() > band) {
+            None => Some(0.0),
+            Some(i) if i + 1 < self.output.len() => Some((i + 1) as f32 * self.dt),
+            Some(_) => None,
+        };
+
+        //without a step there's nothing to rise to, so the overshoot is how far the output
+        //strays from the target
+        if amplitude == 0.0 {
+            let peak = self
+                .output
+                .iter()
+                .map(|&y| (y - target).abs())
+                .fold(0.0, f32::max);
+            return ResponseMetrics {
+                rise_time: (peak <= band).then_some(0.0),
+                overshoot: peak * 100.0,
+                settling_time,
+                steady_state_error: target - last,
+            };
+        }
+
+        // fraction of the way from the initial value to the target
+        let progress = |y: f32| (y - initial) / amplitude;
+        let first_time = |fraction: f32| {
+            self.times()
+                .zip(&self.output)
+                .find(|&(_, &y)| progress(y) >= fraction)
+                .map(|(t, _)| t)
+        };
+
+        let rise_time = first_time(0.1)
+            .zip(first_time(0.9))
+            .map(|(start, end)| end - start);
+
+        let peak = self
+            .output
+            .iter()
+            .map(|&y| progress(y))
+            .fold(f32::NEG_INFINITY, f32::max);
+        let overshoot = (peak - 1.0).max(0.0) * 100.0;
+
+        ResponseMetrics {
+            rise_time,
+            overshoot,
+            settling_time,
+            steady_state_error: target - last,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ResponseMetrics {
+    /// Time taken to go from 10% to 90% of the way to the target, if it gets there.
+    pub rise_time: Option<f32>,
+    /// How far the peak goes past the target, in percent of the step. Without a step, how far
+    /// the output strays from the target, in percent of a unit step.
+    pub overshoot: f32,
+    /// Time after which the output stays within the tolerance of the target, if it does
+    /// before the end of the response.
+    pub settling_time: Option<f32>,
+    /// Difference between the target and the output at the end of the response.
+    pub steady_state_error: f32,
+}
+
+impl DynamicsParams {
+    /// Simulates the response to the target jumping from 0 to 1 at `t = 0`, over `duration`
+    /// seconds with fixed steps of `dt`.
+    pub fn step_response(&self, mode: TickMode, dt: f32, duration: f32) -> Response {
+        self.simulate(mode, dt, duration, |_, _| 1.0)
+    }
+
+    /// Simulates the response to a unit impulse at `t = 0`, i.e. a target of `1 / dt` during
+    /// the first step and 0 afterwards.
+    pub fn impulse_response(&self, mode: TickMode, dt: f32, duration: f32) -> Response {
+        self.simulate(
+            mode,
+            dt,
+            duration,
+            |i, dt| if i == 0 { dt.recip() } else { 0.0 },
+        )
+    }
+
+    /// Simulates the response to a target moving at unit speed from `t = 0`.
+    pub fn ramp_response(&self, mode: TickMode, dt: f32, duration: f32) -> Response {
+        self.simulate(mode, dt, duration, |i, dt| (i + 1) as f32 * dt)
+    }
+
+    /// Simulates the response to an arbitrary input, where `input(i, dt)` is the target
+    /// during step `i`. The output starts at rest at 0. Panics if `dt` isn't positive.
+    pub fn simulate(
+        &self,
+        mode: TickMode,
+        dt: f32,
+        duration: f32,
+        input: impl Fn(usize, f32) -> f32,
+    ) -> Response {
+        assert!(dt > 0.0, "dt must be positive, got {dt}");
+        let steps = (duration / dt).ceil() as usize;
+        let mut state = DynamicsState::new(0.0, *self);
+        let mut response = Response {
+            dt,
+            input: vec![0.0],
+            output: vec![0.0],
+        };
+
+        for i in 0..steps {
+            state.target = input(i, dt);
+            state.tick(dt, None, mode);
+            response.input.push(state.target);
+            response.output.push(state.value());
+        }
+
+        response
+    }
+}
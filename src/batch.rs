@@ -0,0 +1,271 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_time::Time;
+use wide::{f32x8, CmpLe, CmpLt};
+
+use crate::{
+    state::{DynamicsParams, DynamicsSet, TickMode},
+    DynamicsSchedule, ParallelBatchSize,
+};
+
+const WIDTH: usize = 8;
+
+/// Many scalar springs stored as a structure of arrays and ticked eight at a time with SIMD.
+///
+/// Meant for swarms of values that don't need sources, constraints or property bindings:
+/// targets are written and values read directly by index, and the whole batch is ticked in
+/// [`DynamicsSet::Tick`]. A vector value takes one spring per component.
+#[derive(Component, Default)]
+pub struct DynamicsBatch {
+    pub tick_mode: TickMode,
+
+    params: Vec<DynamicsParams>,
+    // all padded to a multiple of `WIDTH`
+    target: Vec<f32>,
+    prev_target: Vec<f32>,
+    current: Vec<f32>,
+    d_current: Vec<f32>,
+    // `k1, k2, k3, w, z, d` of each spring's params, the terms depending on dt are computed
+    // from them each tick
+    coefficients: [Vec<f32>; 6],
+}
+
+impl DynamicsBatch {
+    pub fn new(tick_mode: TickMode) -> Self {
+        Self {
+            tick_mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Adds a spring at rest at `initial`, returning its index.
+    pub fn push(&mut self, initial: f32, params: DynamicsParams) -> usize {
+        let index = self.params.len();
+        //grow by a whole vector once the padding is used up
+        if index == self.target.len() {
+            let padded = index + WIDTH;
+            for values in [
+                &mut self.target,
+                &mut self.prev_target,
+                &mut self.current,
+                &mut self.d_current,
+            ] {
+                values.resize(padded, 0.0);
+            }
+            //padding ticks with the default params, which are well defined for any dt
+            let padding = coefficients(DynamicsParams::default());
+            for (values, padding) in self.coefficients.iter_mut().zip(padding) {
+                values.resize(padded, padding);
+            }
+        }
+
+        self.params.push(params);
+        self.target[index] = initial;
+        self.prev_target[index] = initial;
+        self.current[index] = initial;
+        self.set_params(index, params);
+        index
+    }
+
+    pub fn params(&self, index: usize) -> DynamicsParams {
+        self.params[index]
+    }
+
+    pub fn set_params(&mut self, index: usize, params: DynamicsParams) {
+        self.params[index] = params;
+        for (values, coefficient) in self.coefficients.iter_mut().zip(coefficients(params)) {
+            values[index] = coefficient;
+        }
+    }
+
+    pub fn value(&self, index: usize) -> f32 {
+        self.values()[index]
+    }
+
+    pub fn velocity(&self, index: usize) -> f32 {
+        self.d_current[..self.len()][index]
+    }
+
+    pub fn target(&self, index: usize) -> f32 {
+        self.targets()[index]
+    }
+
+    pub fn set_target(&mut self, index: usize, target: f32) {
+        self.targets_mut()[index] = target;
+    }
+
+    /// Moves the target without generating target velocity.
+    pub fn teleport_target(&mut self, index: usize, target: f32) {
+        self.set_target(index, target);
+        self.prev_target[index] = target;
+    }
+
+    /// Jumps the spring to `value` at rest.
+    pub fn snap_to(&mut self, index: usize, value: f32) {
+        self.teleport_target(index, value);
+        self.current[index] = value;
+        self.d_current[index] = 0.0;
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.current[..self.len()]
+    }
+
+    pub fn targets(&self) -> &[f32] {
+        &self.target[..self.len()]
+    }
+
+    pub fn targets_mut(&mut self) -> &mut [f32] {
+        let len = self.len();
+        &mut self.target[..len]
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        let dt = f32x8::splat(dt);
+        let c = &self.coefficients;
+        for i in (0..self.target.len()).step_by(WIDTH) {
+            let target = load(&self.target, i);
+            let d_target = (target - load(&self.prev_target, i)) / dt;
+            let mut current = load(&self.current, i);
+            let mut d_current = load(&self.d_current, i);
+            let [k1, k2, k3, w, z, d] = c.each_ref().map(|values| load(values, i));
+
+            if self.tick_mode == TickMode::Analytic {
+                let [a, b, c, d] = homogeneous_response(w, z, d, dt);
+                let offset = d_target * (k3 - k1);
+                let start = target - d_target * dt;
+                let error = current - start - offset;
+                let d_error = d_current - d_target;
+
+                current = target + offset + error * a + d_error * b;
+                d_current = d_target + error * c + d_error * d;
+            } else {
+                let (k1, k2) = integration_coefficients(self.tick_mode, k1, k2, w, z, d, dt);
+
+                current += d_current * dt;
+                let error = target - current;
+                let d2_current = (error + d_target * k3 - d_current * k1) / k2;
+                d_current += d2_current * dt;
+            }
+
+            store(&mut self.current, i, current);
+            store(&mut self.d_current, i, d_current);
+        }
+
+        self.prev_target.copy_from_slice(&self.target);
+    }
+}
+
+fn coefficients(params: DynamicsParams) -> [f32; 6] {
+    let DynamicsParams {
+        k1,
+        k2,
+        k3,
+        w,
+        z,
+        d,
+        ..
+    } = params;
+    [k1, k2, k3, w, z, d]
+}
+
+// `DynamicsParams::coefficients` for eight springs at once, returning `k1` and `k2` as `k3`
+// is the same in every mode
+fn integration_coefficients(
+    mode: TickMode,
+    k1: f32x8,
+    k2: f32x8,
+    w: f32x8,
+    z: f32x8,
+    d: f32x8,
+    dt: f32x8,
+) -> (f32x8, f32x8) {
+    let stable_k2 = k2.max(k1 * dt).max((dt * dt + k1 * dt) * 0.5);
+    match mode {
+        TickMode::Simple => (k1, k2),
+        TickMode::Stable => (k1, stable_k2),
+        TickMode::PoleMatching | TickMode::Analytic => {
+            //both branches are computed, and each lane picks its own
+            let t1 = (-z * w * dt).exp();
+            let cosh = {
+                let e = (d * dt).exp();
+                (e + e.recip()) * 0.5
+            };
+            let alpha = 2.0 * t1 * z.cmp_le(f32x8::ONE).blend((d * dt).cos(), cosh);
+            let beta = t1 * t1;
+            let t2 = dt / (beta - alpha + 1.0);
+
+            let slow = (w * dt).cmp_lt(z);
+            (
+                slow.blend(k1, t2 * (1.0 - beta)),
+                slow.blend(stable_k2, t2 * dt),
+            )
+        }
+    }
+}
+
+// `DynamicsParams::homogeneous_response` for eight springs at once
+fn homogeneous_response(w: f32x8, z: f32x8, d: f32x8, t: f32x8) -> [f32x8; 4] {
+    let zw = z * w;
+
+    //all three branches are computed, and each lane picks its own
+    let e = (-zw * t).exp();
+    let (sin, cos) = (d * t).sin_cos();
+    let p = ((d - zw) * t).exp();
+    let q = (-(d + zw) * t).exp();
+
+    let first_order = (d * t).cmp_lt(f32x8::splat(1e-4));
+    let underdamped = z.cmp_lt(f32x8::ONE);
+    let cos = first_order.blend(e, underdamped.blend(e * cos, (p + q) * 0.5));
+    let sin_over_d = first_order.blend(e * t, underdamped.blend(e * sin / d, (p - q) / (2.0 * d)));
+
+    [
+        cos + zw * sin_over_d,
+        sin_over_d,
+        -w * w * sin_over_d,
+        cos - zw * sin_over_d,
+    ]
+}
+
+fn load(values: &[f32], i: usize) -> f32x8 {
+    f32x8::new(values[i..i + WIDTH].try_into().unwrap())
+}
+
+fn store(values: &mut [f32], i: usize, x: f32x8) {
+    values[i..i + WIDTH].copy_from_slice(&x.to_array());
+}
+
+pub(crate) struct DynamicsBatchPlugin;
+
+impl Plugin for DynamicsBatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            DynamicsSchedule::of(app),
+            tick_batches.in_set(DynamicsSet::Tick),
+        );
+    }
+}
+
+fn tick_batches(
+    mut batches: Query<&mut DynamicsBatch>,
+    time: Res<Time>,
+    batch_size: Res<ParallelBatchSize>,
+) {
+    let dt = time.delta_secs();
+    batches
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|mut batch| batch.tick(dt));
+}
@@ -0,0 +1,260 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use bevy_color::{Color, Hsla, Hsva, Hwba, Laba, Lcha, LinearRgba, Oklaba, Oklcha, Srgba, Xyza};
+use bevy_ecs::component::Component;
+use bevy_math::VectorSpace;
+use bevy_reflect::{Reflect, TypePath};
+
+use crate::{
+    props::ComputedProperty,
+    wrapping::{Degrees, Wrapping},
+    AnimValue,
+};
+
+/// A color space that dynamics can run in.
+pub trait DynamicsColorSpace: TypePath + Send + Sync + 'static {
+    type Value: AnimValue;
+
+    fn from_color(color: Color) -> Self::Value;
+    fn to_color(value: Self::Value) -> Color;
+}
+
+macro_rules! impl_linear_color_space {
+    ($($space: ident),*) => {
+        $(
+            impl DynamicsColorSpace for $space {
+                type Value = $space;
+
+                fn from_color(color: Color) -> $space {
+                    color.into()
+                }
+
+                fn to_color(value: $space) -> Color {
+                    value.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_linear_color_space!(Oklaba, LinearRgba, Srgba, Laba, Xyza);
+
+impl DynamicsColorSpace for Hsla {
+    type Value = PolarColor;
+
+    fn from_color(color: Color) -> PolarColor {
+        let Hsla {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        } = color.into();
+        PolarColor::new(lightness, saturation, hue, alpha)
+    }
+
+    fn to_color(value: PolarColor) -> Color {
+        Hsla::new(
+            value.hue(),
+            value.colorfulness,
+            value.lightness,
+            value.alpha,
+        )
+        .into()
+    }
+}
+
+impl DynamicsColorSpace for Oklcha {
+    type Value = PolarColor;
+
+    fn from_color(color: Color) -> PolarColor {
+        let Oklcha {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        } = color.into();
+        PolarColor::new(lightness, chroma, hue, alpha)
+    }
+
+    fn to_color(value: PolarColor) -> Color {
+        Oklcha::new(
+            value.lightness,
+            value.colorfulness,
+            value.hue(),
+            value.alpha,
+        )
+        .into()
+    }
+}
+
+/// A color in a cylindrical space such as [`Hsla`] or [`Oklcha`]. The hue is a
+/// [`Wrapping`] angle in degrees, so dynamics take the shortest way around the wheel
+/// instead of wrapping from 359° back through 0°, and keep the colorfulness while they do.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Reflect)]
+pub struct PolarColor {
+    pub lightness: f32,
+    /// The saturation in [`Hsla`], the chroma in [`Oklcha`].
+    pub colorfulness: f32,
+    pub hue: Wrapping<Degrees>,
+    pub alpha: f32,
+}
+
+impl PolarColor {
+    pub fn new(lightness: f32, colorfulness: f32, hue: f32, alpha: f32) -> Self {
+        Self {
+            lightness,
+            colorfulness,
+            hue: Wrapping::new(hue),
+            alpha,
+        }
+    }
+
+    /// The hue in degrees, in `[0, 360)`.
+    pub fn hue(&self) -> f32 {
+        self.hue.wrapped()
+    }
+}
+
+impl Add for PolarColor {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            lightness: self.lightness + rhs.lightness,
+            colorfulness: self.colorfulness + rhs.colorfulness,
+            hue: self.hue + rhs.hue,
+            alpha: self.alpha + rhs.alpha,
+        }
+    }
+}
+
+impl Sub for PolarColor {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            lightness: self.lightness - rhs.lightness,
+            colorfulness: self.colorfulness - rhs.colorfulness,
+            hue: self.hue - rhs.hue,
+            alpha: self.alpha - rhs.alpha,
+        }
+    }
+}
+
+impl Mul<f32> for PolarColor {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            lightness: self.lightness * rhs,
+            colorfulness: self.colorfulness * rhs,
+            hue: self.hue * rhs,
+            alpha: self.alpha * rhs,
+        }
+    }
+}
+
+impl Div<f32> for PolarColor {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self {
+        Self {
+            lightness: self.lightness / rhs,
+            colorfulness: self.colorfulness / rhs,
+            hue: self.hue / rhs,
+            alpha: self.alpha / rhs,
+        }
+    }
+}
+
+impl Neg for PolarColor {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -1.0
+    }
+}
+
+impl AnimValue for PolarColor {
+    const LANES: usize = 4;
+
+    fn lane(&self, index: usize) -> f32 {
+        match index {
+            0 => self.lightness,
+            1 => self.colorfulness,
+            2 => self.hue.0,
+            3 => self.alpha,
+            _ => panic!("lane index out of bounds"),
+        }
+    }
+
+    fn lane_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.lightness,
+            1 => &mut self.colorfulness,
+            2 => &mut self.hue.0,
+            3 => &mut self.alpha,
+            _ => panic!("lane index out of bounds"),
+        }
+    }
+
+    fn difference(self, other: Self) -> Self {
+        Self {
+            hue: self.hue.difference(other.hue),
+            ..self - other
+        }
+    }
+}
+
+impl VectorSpace for PolarColor {
+    const ZERO: Self = Self {
+        lightness: 0.0,
+        colorfulness: 0.0,
+        hue: Wrapping::new(0.0),
+        alpha: 0.0,
+    };
+}
+
+/// A [`Color`] field of a component.
+pub trait ColorField: TypePath + Send + Sync + 'static {
+    type Component: Component;
+
+    fn color_mut(component: &mut Self::Component) -> &mut Color;
+}
+
+/// Animates any [`Color`] field by running dynamics in the color space `S`, writing the
+/// result back in whichever variant the field already holds.
+#[derive(TypePath)]
+pub struct ColorProperty<F: ColorField, S: DynamicsColorSpace = Oklaba>(PhantomData<(F, S)>);
+
+impl<F: ColorField, S: DynamicsColorSpace> ComputedProperty for ColorProperty<F, S> {
+    type Component = F::Component;
+    type Property = S::Value;
+
+    fn set(component: &mut F::Component, value: S::Value) {
+        let color = F::color_mut(component);
+        *color = convert_like(S::to_color(value), color);
+    }
+}
+
+/// Reads `color` in the dynamics color space `S`, e.g. to get the initial value of a handle
+/// animated with a [`ColorProperty`].
+pub fn to_space<S: DynamicsColorSpace>(color: impl Into<Color>) -> S::Value {
+    S::from_color(color.into())
+}
+
+fn convert_like(color: Color, like: &Color) -> Color {
+    match like {
+        Color::Srgba(_) => Srgba::from(color).into(),
+        Color::LinearRgba(_) => LinearRgba::from(color).into(),
+        Color::Hsla(_) => Hsla::from(color).into(),
+        Color::Hsva(_) => Hsva::from(color).into(),
+        Color::Hwba(_) => Hwba::from(color).into(),
+        Color::Laba(_) => Laba::from(color).into(),
+        Color::Lcha(_) => Lcha::from(color).into(),
+        Color::Oklaba(_) => Oklaba::from(color).into(),
+        Color::Oklcha(_) => Oklcha::from(color).into(),
+        Color::Xyza(_) => Xyza::from(color).into(),
+    }
+}
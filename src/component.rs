@@ -1,90 +1,303 @@
+use bevy_asset::Assets;
 use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
     component::Component,
-    entity::Entity,
+    entity::{Entity, EntityMapper, MapEntities},
     prelude::{Query, Without},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    system::{Res, ResMut, Resource},
     world::{EntityMutExcept, EntityRef},
 };
-use bevy_utils::TypeIdMap;
-use std::{any::TypeId, ops::DerefMut};
+use bevy_hierarchy::Parent;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::{tracing::warn, HashMap};
+use std::ops::DerefMut;
 
 use bevy_animation::prelude::AnimatableProperty;
 
 use crate::{
+    props::{ComputedProperty, MaterialProperty, TransformProperty, WorldSpaceProperty},
     state::{AnimHandle, DynamicsState},
-    AnimValue,
+    transform::TransformDynamicsState,
+    AnimValue, ParallelBatchSize,
 };
 
-#[derive(Component)]
+pub(crate) type DynamicsDestination<'w> = EntityMutExcept<'w, (Dynamics, Parent, GlobalTransform)>;
+
+// writes the value of the handle in `source` to a property of the destination
+pub(crate) type ApplyProperty = fn(EntityRef, &mut DynamicsDestination, Option<&GlobalTransform>);
+
+/// Properties that bindings read from a scene can be resolved to, by type path.
+#[derive(Resource, Default)]
+pub(crate) struct PropertyRegistry(HashMap<String, ApplyProperty>);
+
+impl PropertyRegistry {
+    pub fn register<P: TypePath>(&mut self, apply: ApplyProperty) {
+        self.0.insert(P::type_path().to_owned(), apply);
+    }
+}
+
+/// Properties of an entity written from handles. Bindings are stored by the type path of
+/// their property, so they can be saved and loaded with the entity, and are resolved again
+/// through the properties registered with [`DynamicsAppExt`](crate::DynamicsAppExt).
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default, MapEntities)]
 pub struct Dynamics {
-    props: TypeIdMap<Box<dyn PropertyUpdate>>,
+    bindings: Vec<PropertyBinding>,
+}
+
+/// A property of an entity written from a handle, see [`Dynamics`].
+#[derive(Clone, Debug, Reflect)]
+pub struct PropertyBinding {
+    property: String,
+    source: Entity,
+    #[reflect(ignore)]
+    apply: Option<ApplyProperty>,
+}
+
+impl PropertyBinding {
+    /// The type path of the property.
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The entity of the handle the property is written from.
+    pub fn source(&self) -> Entity {
+        self.source
+    }
 }
 
 impl Dynamics {
-    pub(crate) fn new() -> Self {
-        Self {
-            props: Default::default(),
-        }
+    pub fn bindings(&self) -> &[PropertyBinding] {
+        &self.bindings
     }
 
     pub fn add<P: AnimatableProperty<Property: AnimValue>>(
         &mut self,
         source: AnimHandle<P::Property>,
     ) {
-        self.props
-            .insert(TypeId::of::<P>(), Box::new(PropertyWrapper::<P> { source }));
+        self.bind::<P>(source.entity, apply_property::<P>);
     }
 
     pub fn remove<P: AnimatableProperty<Property: AnimValue>>(&mut self) {
-        self.props.remove(&TypeId::of::<P>());
+        self.unbind::<P>();
+    }
+
+    pub fn add_world<P: WorldSpaceProperty>(&mut self, source: AnimHandle<P::Property>) {
+        self.bind::<P>(source.entity, apply_world_property::<P>);
+    }
+
+    pub fn remove_world<P: WorldSpaceProperty>(&mut self) {
+        self.unbind::<P>();
+    }
+
+    pub fn add_computed<P: ComputedProperty>(&mut self, source: AnimHandle<P::Property>) {
+        self.bind::<P>(source.entity, apply_computed_property::<P>);
+    }
+
+    pub fn remove_computed<P: ComputedProperty>(&mut self) {
+        self.unbind::<P>();
+    }
+
+    pub fn add_transform(&mut self, source: AnimHandle<Transform>) {
+        self.bind::<TransformProperty>(source.entity, apply_transform);
+    }
+
+    pub fn remove_transform(&mut self) {
+        self.unbind::<TransformProperty>();
+    }
+
+    fn bind<P: TypePath>(&mut self, source: Entity, apply: ApplyProperty) {
+        let binding = PropertyBinding {
+            property: P::type_path().to_owned(),
+            source,
+            apply: Some(apply),
+        };
+        match self
+            .bindings
+            .iter_mut()
+            .find(|existing| existing.property == binding.property)
+        {
+            Some(existing) => *existing = binding,
+            None => self.bindings.push(binding),
+        }
+    }
+
+    fn unbind<P: TypePath>(&mut self) {
+        self.bindings
+            .retain(|binding| binding.property != P::type_path());
     }
 
     fn apply(
         &self,
         sources: &Query<EntityRef, Without<Dynamics>>,
-        mut destination: EntityMutExcept<Dynamics>,
+        mut destination: DynamicsDestination,
+        parent: Option<&GlobalTransform>,
     ) {
-        self.props.values().for_each(|prop| {
-            if let Ok(source) = sources.get(prop.source()) {
-                prop.apply(source, &mut destination);
+        for binding in &self.bindings {
+            let Some(apply) = binding.apply else {
+                continue;
+            };
+            if let Ok(source) = sources.get(binding.source) {
+                apply(source, &mut destination, parent);
             }
-        })
+        }
     }
 }
 
-trait PropertyUpdate: Send + Sync + 'static {
-    fn source(&self) -> Entity;
-    fn apply(&self, source: EntityRef, destination: &mut EntityMutExcept<Dynamics>);
+impl MapEntities for Dynamics {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for binding in &mut self.bindings {
+            binding.source = entity_mapper.map_entity(binding.source);
+        }
+    }
 }
 
-struct PropertyWrapper<P: AnimatableProperty<Property: AnimValue>> {
-    source: AnimHandle<P::Property>,
+pub(crate) fn apply_property<P: AnimatableProperty<Property: AnimValue>>(
+    source: EntityRef,
+    destination: &mut DynamicsDestination,
+    _parent: Option<&GlobalTransform>,
+) {
+    let Some(state) = source.get::<DynamicsState<P::Property>>() else {
+        return;
+    };
+    let Some(mut component) = destination.get_mut::<P::Component>() else {
+        return;
+    };
+    let Some(prop) = P::get_mut(component.deref_mut()) else {
+        return;
+    };
+
+    *prop = state.value();
 }
 
-impl<P: AnimatableProperty<Property: AnimValue>> PropertyUpdate for PropertyWrapper<P> {
-    fn source(&self) -> Entity {
-        self.source.entity
-    }
+pub(crate) fn apply_world_property<P: WorldSpaceProperty>(
+    source: EntityRef,
+    destination: &mut DynamicsDestination,
+    parent: Option<&GlobalTransform>,
+) {
+    let Some(state) = source.get::<DynamicsState<P::Property>>() else {
+        return;
+    };
+    let Some(mut component) = destination.get_mut::<P::Component>() else {
+        return;
+    };
 
-    fn apply(&self, source: EntityRef, destination: &mut EntityMutExcept<Dynamics>) {
-        let Some(state) = source.get::<DynamicsState<P::Property>>() else {
-            return;
-        };
-        let Some(mut component) = destination.get_mut::<P::Component>() else {
-            return;
-        };
-        let Some(prop) = P::get_mut(component.deref_mut()) else {
-            return;
-        };
+    let parent = parent.copied().unwrap_or(GlobalTransform::IDENTITY);
+    P::set_world(component.deref_mut(), &parent, state.value());
+}
 
-        *prop = state.value();
+pub(crate) fn apply_computed_property<P: ComputedProperty>(
+    source: EntityRef,
+    destination: &mut DynamicsDestination,
+    _parent: Option<&GlobalTransform>,
+) {
+    let Some(state) = source.get::<DynamicsState<P::Property>>() else {
+        return;
+    };
+    let Some(mut component) = destination.get_mut::<P::Component>() else {
+        return;
+    };
+
+    P::set(component.deref_mut(), state.value());
+}
+
+pub(crate) fn apply_transform(
+    source: EntityRef,
+    destination: &mut DynamicsDestination,
+    _parent: Option<&GlobalTransform>,
+) {
+    let Some(state) = source.get::<TransformDynamicsState>() else {
+        return;
+    };
+    let Some(mut transform) = destination.get_mut::<Transform>() else {
+        return;
+    };
+
+    *transform = state.value();
+}
+
+// resolves bindings added without their property, e.g. by loading a scene, and retries the
+// unresolved ones when properties are registered
+pub(super) fn resolve_bindings(
+    mut dynamics: Query<&mut Dynamics>,
+    registry: Res<PropertyRegistry>,
+) {
+    for mut dynamics in &mut dynamics {
+        if !dynamics.is_changed() && !registry.is_changed() {
+            continue;
+        }
+        //bypass change detection, so unresolved bindings are only looked up again when changed
+        for binding in &mut dynamics.bypass_change_detection().bindings {
+            if binding.apply.is_some() {
+                continue;
+            }
+            binding.apply = registry.0.get(&binding.property).copied();
+            if binding.apply.is_none() {
+                warn!(
+                    "no property `{}` is registered, so its binding is skipped; register it with `register_property`, `register_world_property` or `register_computed_property`",
+                    binding.property
+                );
+            }
+        }
     }
 }
 
 pub(super) fn apply_dynamics(
-    mut dynamics: Query<(EntityMutExcept<Dynamics>, &Dynamics)>,
+    mut dynamics: Query<(DynamicsDestination, &Dynamics, Option<&Parent>)>,
     sources: Query<EntityRef, Without<Dynamics>>,
+    transforms: Query<&GlobalTransform>,
+    batch_size: Res<ParallelBatchSize>,
+) {
+    //each destination only writes its own components, and sources and parent transforms are
+    //excluded from those, so destinations can be written in parallel
+    dynamics
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|(entity, dynamics, parent)| {
+            let parent = parent.and_then(|parent| transforms.get(parent.get()).ok());
+            dynamics.apply(&sources, entity, parent);
+        });
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct MaterialDynamics<P: MaterialProperty> {
+    pub(crate) source: AnimHandle<P::Property>,
+}
+
+impl<P: MaterialProperty> MapEntities for MaterialDynamics<P> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.source.map_entities(entity_mapper);
+    }
+}
+
+impl<P: MaterialProperty> MaterialDynamics<P> {
+    pub fn new(source: AnimHandle<P::Property>) -> Self {
+        Self { source }
+    }
+}
+
+pub(crate) fn apply_material_dynamics<P: MaterialProperty>(
+    bindings: Query<(&P::Handle, &MaterialDynamics<P>)>,
+    sources: Query<&DynamicsState<P::Property>>,
+    materials: Option<ResMut<Assets<P::Material>>>,
 ) {
-    for (entity, dynamics) in &mut dynamics {
-        dynamics.apply(&sources, entity);
+    let Some(mut materials) = materials else {
+        return;
+    };
+    for (handle, binding) in &bindings {
+        let Ok(state) = sources.get(binding.source.entity) else {
+            continue;
+        };
+        let Some(material) = materials.get_mut(P::material(handle)) else {
+            continue;
+        };
+        let Some(prop) = P::get_mut(material) else {
+            continue;
+        };
+
+        *prop = state.value();
     }
 }
@@ -0,0 +1,159 @@
+use bevy_math::ops::FloatPow;
+use bevy_reflect::Reflect;
+
+use crate::AnimValue;
+
+/// What happens to the velocity of a component that hits one of its bounds.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Reflect)]
+pub enum BoundResponse {
+    /// Only the value is clamped, the velocity is left as is.
+    #[default]
+    Clamp,
+    /// The value is clamped and the velocity reflected, scaled by `restitution`.
+    Bounce { restitution: f32 },
+    /// The value is clamped and the velocity zeroed.
+    Stop,
+}
+
+/// A limit on the magnitude of a velocity or acceleration.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub enum Limit<T: AnimValue> {
+    /// Each component is clamped on its own.
+    PerComponent(T),
+    /// The whole vector is scaled down to the given length, keeping its direction.
+    Norm(f32),
+}
+
+impl<T: AnimValue> Limit<T> {
+    pub(crate) fn apply(&self, mut value: T) -> T {
+        match *self {
+            Limit::PerComponent(limit) => {
+                for i in 0..T::LANES {
+                    //unlike `clamp`, doesn't panic on a NaN limit
+                    let max = limit.lane(i).abs();
+                    let lane = value.lane_mut(i);
+                    *lane = lane.min(max).max(-max);
+                }
+                value
+            }
+            //ignored like a NaN component limit
+            Limit::Norm(max) if max.is_nan() => value,
+            Limit::Norm(max) => {
+                //a negative limit stops the vector rather than flipping it, and the norm is
+                //only divided by when it's positive
+                let max = max.max(0.0);
+                let norm = norm(value);
+                if norm > max {
+                    value * (max / norm)
+                } else {
+                    value
+                }
+            }
+        }
+    }
+}
+
+/// Limits applied while ticking a handle. Bounds apply per component, while speed and
+/// acceleration may be limited per component or by their norm.
+#[derive(Default, Copy, Clone, Debug, Reflect)]
+pub struct DynamicsConstraints<T: AnimValue> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+    pub response: BoundResponse,
+    pub max_speed: Option<Limit<T>>,
+    pub max_acceleration: Option<Limit<T>>,
+}
+
+impl<T: AnimValue> DynamicsConstraints<T> {
+    pub fn bounded(min: T, max: T) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn with_max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn with_response(mut self, response: BoundResponse) -> Self {
+        self.response = response;
+        self
+    }
+
+    pub fn with_max_speed(mut self, max_speed: T) -> Self {
+        self.max_speed = Some(Limit::PerComponent(max_speed));
+        self
+    }
+
+    pub fn with_max_speed_norm(mut self, max_speed: f32) -> Self {
+        self.max_speed = Some(Limit::Norm(max_speed));
+        self
+    }
+
+    pub fn with_max_acceleration(mut self, max_acceleration: T) -> Self {
+        self.max_acceleration = Some(Limit::PerComponent(max_acceleration));
+        self
+    }
+
+    pub fn with_max_acceleration_norm(mut self, max_acceleration: f32) -> Self {
+        self.max_acceleration = Some(Limit::Norm(max_acceleration));
+        self
+    }
+
+    // both limits only ever shrink the velocity and acceleration computed by the solver, so
+    // they don't undo the stabilized coefficients of `TickMode::Stable` and `TickMode::PoleMatching`
+    pub(crate) fn limit_speed(&self, velocity: T) -> T {
+        match self.max_speed {
+            Some(limit) => limit.apply(velocity),
+            None => velocity,
+        }
+    }
+
+    pub(crate) fn limit_acceleration(&self, acceleration: T) -> T {
+        match self.max_acceleration {
+            Some(limit) => limit.apply(acceleration),
+            None => acceleration,
+        }
+    }
+
+    pub(crate) fn apply_bounds(&self, value: &mut T, velocity: &mut T) {
+        if self.min.is_none() && self.max.is_none() {
+            return;
+        }
+
+        for i in 0..T::LANES {
+            let lane = value.lane_mut(i);
+            let (bound, outward) = match (self.min, self.max) {
+                (Some(min), _) if *lane < min.lane(i) => (min.lane(i), -1.0),
+                (_, Some(max)) if *lane > max.lane(i) => (max.lane(i), 1.0),
+                _ => continue,
+            };
+            *lane = bound;
+
+            let lane_velocity = velocity.lane_mut(i);
+            if *lane_velocity * outward <= 0.0 {
+                continue;
+            }
+            match self.response {
+                BoundResponse::Clamp => {}
+                BoundResponse::Bounce { restitution } => *lane_velocity *= -restitution,
+                BoundResponse::Stop => *lane_velocity = 0.0,
+            }
+        }
+    }
+}
+
+pub(crate) fn norm<T: AnimValue>(value: T) -> f32 {
+    (0..T::LANES)
+        .map(|i| value.lane(i).squared())
+        .sum::<f32>()
+        .sqrt()
+}
@@ -1,23 +1,102 @@
 use bevy_animation::prelude::AnimatableProperty;
 use bevy_app::App;
+use bevy_color::{Hsla, Laba, LinearRgba, Oklaba, Oklcha, Srgba, Xyza};
+use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::{Commands, EntityCommands};
+use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::system::SystemId;
+use bevy_transform::components::Transform;
 
 use crate::state::TickPoleMatching;
 use crate::{
-    component::Dynamics,
-    state::{AnimHandle, AnimValuePlugin, DynamicsParams, DynamicsSource, DynamicsState},
-    AnimValue,
+    color::{ColorField, ColorProperty},
+    component::{
+        apply_computed_property, apply_material_dynamics, apply_property, apply_world_property,
+        Dynamics, MaterialDynamics, PropertyRegistry,
+    },
+    constraint::DynamicsConstraints,
+    path::PathDynamics,
+    props::{ComputedProperty, MaterialProperty, WorldSpaceProperty},
+    shake::CameraShake,
+    solver::{Solver, SolverPlugin, SolverState},
+    state::{
+        self, AnimHandle, AnimValuePlugin, DynamicsParams, DynamicsSet, DynamicsSource,
+        DynamicsState, TickMode,
+    },
+    transform::{TransformDynamicsState, TransformParams},
+    AnimValue, DynamicsSchedule,
 };
 
+/// The `init_*` methods add systems to the schedule of [`DynamicsPlugin`](crate::DynamicsPlugin),
+/// so they panic if it hasn't been added yet.
 pub trait DynamicsAppExt {
     fn init_animatable_type<T: AnimValue>(&mut self) -> &mut Self;
+    fn init_material_property<P: MaterialProperty>(&mut self) -> &mut Self;
+    /// Ticks handles of `T` driven by the solver `S`. Built-in solvers are opt-in like custom
+    /// ones, and registering a solver more than once has no effect.
+    fn init_solver<T: AnimValue, S: Solver<T>>(&mut self) -> &mut Self;
+
+    /// Lets [`Dynamics`] bindings of `P` be resolved by type path, e.g. after loading them
+    /// from a scene. The built-in properties are registered by
+    /// [`DynamicsPlugin`](crate::DynamicsPlugin), custom ones have to be registered to be
+    /// loaded even if they're also bound with [`animate`](DynamicsEntityCommandsExt::animate).
+    fn register_property<P: AnimatableProperty<Property: AnimValue>>(&mut self) -> &mut Self;
+    fn register_world_property<P: WorldSpaceProperty>(&mut self) -> &mut Self;
+    fn register_computed_property<P: ComputedProperty>(&mut self) -> &mut Self;
+    /// Registers the [`ColorProperty`] of `F` in every built-in color space.
+    fn register_color_field<F: ColorField>(&mut self) -> &mut Self;
 }
 
 impl DynamicsAppExt for App {
     fn init_animatable_type<T: AnimValue>(&mut self) -> &mut Self {
         self.add_plugins(AnimValuePlugin::<T>::default())
     }
+
+    fn init_material_property<P: MaterialProperty>(&mut self) -> &mut Self {
+        self.register_type::<MaterialDynamics<P>>();
+        self.add_systems(
+            DynamicsSchedule::of(self),
+            apply_material_dynamics::<P>.in_set(DynamicsSet::Write),
+        )
+    }
+
+    fn init_solver<T: AnimValue, S: Solver<T>>(&mut self) -> &mut Self {
+        if !self.is_plugin_added::<SolverPlugin<T, S>>() {
+            self.add_plugins(SolverPlugin::<T, S>::default());
+        }
+        self
+    }
+
+    fn register_property<P: AnimatableProperty<Property: AnimValue>>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PropertyRegistry>()
+            .register::<P>(apply_property::<P>);
+        self
+    }
+
+    fn register_world_property<P: WorldSpaceProperty>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PropertyRegistry>()
+            .register::<P>(apply_world_property::<P>);
+        self
+    }
+
+    fn register_computed_property<P: ComputedProperty>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PropertyRegistry>()
+            .register::<P>(apply_computed_property::<P>);
+        self
+    }
+
+    fn register_color_field<F: ColorField>(&mut self) -> &mut Self {
+        self.register_computed_property::<ColorProperty<F, Oklaba>>()
+            .register_computed_property::<ColorProperty<F, LinearRgba>>()
+            .register_computed_property::<ColorProperty<F, Srgba>>()
+            .register_computed_property::<ColorProperty<F, Laba>>()
+            .register_computed_property::<ColorProperty<F, Xyza>>()
+            .register_computed_property::<ColorProperty<F, Hsla>>()
+            .register_computed_property::<ColorProperty<F, Oklcha>>()
+    }
 }
 
 pub trait DynamicsCommandsExt {
@@ -27,6 +106,88 @@ pub trait DynamicsCommandsExt {
         params: DynamicsParams,
         source: SystemId<(), T>,
     ) -> AnimHandle<T>;
+
+    /// Like [`animate_value`](DynamicsCommandsExt::animate_value), but drives the handle with
+    /// the solver `S` instead of second-order dynamics. `S` has to be registered for `T` with
+    /// [`init_solver`](DynamicsAppExt::init_solver).
+    fn animate_with_solver<T: AnimValue, S: Solver<T>>(
+        &mut self,
+        initial: T,
+        params: S::Params,
+        source: SystemId<(), T>,
+    ) -> AnimHandle<T>;
+
+    fn animate_transform(
+        &mut self,
+        initial: Transform,
+        params: impl Into<TransformParams>,
+        source: SystemId<(), Transform>,
+    ) -> AnimHandle<Transform>;
+
+    fn set_constraints<T: AnimValue>(
+        &mut self,
+        handle: AnimHandle<T>,
+        constraints: DynamicsConstraints<T>,
+    );
+
+    fn set_tick_mode<T: Send + Sync + 'static>(&mut self, handle: AnimHandle<T>, mode: TickMode);
+
+    /// Replaces the source of a handle. Sources are systems, so they aren't saved with the
+    /// handle and have to be set again after loading it.
+    fn set_source<T: Send + Sync + 'static>(
+        &mut self,
+        handle: AnimHandle<T>,
+        source: SystemId<(), T>,
+    );
+
+    /// Gives each lane of the handle its own params, e.g. `[loose, stiff, loose]` for a
+    /// [`Vec3`](bevy_math::Vec3) following loosely on the horizontal plane only. Params whose
+    /// number doesn't match [`AnimValue::LANES`] are ignored with a warning when applied.
+    fn set_axis_params<T: AnimValue>(
+        &mut self,
+        handle: AnimHandle<T>,
+        params: impl Into<Vec<DynamicsParams>>,
+    );
+
+    /// Replaces the params of a handle driven by the solver `S`, resetting its solver state.
+    fn set_solver_params<T: AnimValue, S: Solver<T>>(
+        &mut self,
+        handle: AnimHandle<T>,
+        params: S::Params,
+    );
+
+    /// Jumps the handle to `value` at rest, without generating any target velocity. The state
+    /// of its solver, if it has one, starts over.
+    fn snap_to<T: AnimValue>(&mut self, handle: AnimHandle<T>, value: T);
+
+    fn reset_velocity<T: AnimValue>(&mut self, handle: AnimHandle<T>);
+
+    fn set_state<T: AnimValue>(&mut self, handle: AnimHandle<T>, value: T, velocity: T);
+
+    /// Moves the target without generating target velocity, so a jump in the target (or in
+    /// the source on the next frame) isn't fed forward into the dynamics. The state of its
+    /// solver, if it has one, starts over.
+    fn teleport_target<T: AnimValue>(&mut self, handle: AnimHandle<T>, target: T);
+
+    /// Like [`snap_to`](DynamicsCommandsExt::snap_to), for a handle from
+    /// [`animate_transform`](DynamicsCommandsExt::animate_transform).
+    fn snap_transform_to(&mut self, handle: AnimHandle<Transform>, value: Transform);
+
+    fn reset_transform_velocity(&mut self, handle: AnimHandle<Transform>);
+
+    /// Like [`teleport_target`](DynamicsCommandsExt::teleport_target), for a handle from
+    /// [`animate_transform`](DynamicsCommandsExt::animate_transform).
+    fn teleport_transform_target(&mut self, handle: AnimHandle<Transform>, target: Transform);
+
+    /// Adds `impulse` to the velocity of the handle.
+    fn apply_impulse<T: AnimValue>(&mut self, handle: AnimHandle<T>, impulse: T);
+
+    /// Accelerates the handle by `force` on its next tick. Forces accumulate until the handle
+    /// is ticked, so a sustained force has to be applied every frame.
+    fn apply_force<T: AnimValue>(&mut self, handle: AnimHandle<T>, force: T);
+
+    /// Adds trauma to the [`CameraShake`] of `entity`, if it has one.
+    fn add_trauma(&mut self, entity: Entity, amount: f32);
 }
 
 impl<'w, 's> DynamicsCommandsExt for Commands<'w, 's> {
@@ -45,6 +206,143 @@ impl<'w, 's> DynamicsCommandsExt for Commands<'w, 's> {
             .id();
         AnimHandle::new(id)
     }
+
+    fn animate_with_solver<T: AnimValue, S: Solver<T>>(
+        &mut self,
+        initial: T,
+        params: S::Params,
+        source: SystemId<(), T>,
+    ) -> AnimHandle<T> {
+        let id = self
+            .spawn((
+                DynamicsState::new(initial, DynamicsParams::default()),
+                DynamicsSource(source),
+                SolverState::<T, S>::new(params),
+            ))
+            .id();
+        AnimHandle::new(id)
+    }
+
+    fn animate_transform(
+        &mut self,
+        initial: Transform,
+        params: impl Into<TransformParams>,
+        source: SystemId<(), Transform>,
+    ) -> AnimHandle<Transform> {
+        let id = self
+            .spawn((
+                TransformDynamicsState::new(initial, params.into()),
+                DynamicsSource(source),
+                TickPoleMatching,
+            ))
+            .id();
+        AnimHandle::new(id)
+    }
+
+    fn set_constraints<T: AnimValue>(
+        &mut self,
+        handle: AnimHandle<T>,
+        constraints: DynamicsConstraints<T>,
+    ) {
+        modify_state(self, handle, move |state| state.constraints = constraints);
+    }
+
+    fn set_tick_mode<T: Send + Sync + 'static>(&mut self, handle: AnimHandle<T>, mode: TickMode) {
+        state::set_tick_mode(&mut self.entity(handle.entity), mode);
+    }
+
+    fn set_source<T: Send + Sync + 'static>(
+        &mut self,
+        handle: AnimHandle<T>,
+        source: SystemId<(), T>,
+    ) {
+        self.entity(handle.entity).insert(DynamicsSource(source));
+    }
+
+    fn set_axis_params<T: AnimValue>(
+        &mut self,
+        handle: AnimHandle<T>,
+        params: impl Into<Vec<DynamicsParams>>,
+    ) {
+        let params = params.into();
+        modify_state(self, handle, move |state| {
+            state.set_axis_params(Some(params))
+        });
+    }
+
+    fn set_solver_params<T: AnimValue, S: Solver<T>>(
+        &mut self,
+        handle: AnimHandle<T>,
+        params: S::Params,
+    ) {
+        self.entity(handle.entity)
+            .entry::<SolverState<T, S>>()
+            .and_modify(move |mut solver| solver.set_params(params));
+    }
+
+    fn snap_to<T: AnimValue>(&mut self, handle: AnimHandle<T>, value: T) {
+        modify_state(self, handle, move |state| state.snap_to(value));
+    }
+
+    fn reset_velocity<T: AnimValue>(&mut self, handle: AnimHandle<T>) {
+        modify_state(self, handle, DynamicsState::reset_velocity);
+    }
+
+    fn set_state<T: AnimValue>(&mut self, handle: AnimHandle<T>, value: T, velocity: T) {
+        modify_state(self, handle, move |state| state.set_state(value, velocity));
+    }
+
+    fn teleport_target<T: AnimValue>(&mut self, handle: AnimHandle<T>, target: T) {
+        modify_state(self, handle, move |state| state.teleport_target(target));
+    }
+
+    fn snap_transform_to(&mut self, handle: AnimHandle<Transform>, value: Transform) {
+        modify_transform_state(self, handle, move |state| state.snap_to(value));
+    }
+
+    fn reset_transform_velocity(&mut self, handle: AnimHandle<Transform>) {
+        modify_transform_state(self, handle, TransformDynamicsState::reset_velocity);
+    }
+
+    fn teleport_transform_target(&mut self, handle: AnimHandle<Transform>, target: Transform) {
+        modify_transform_state(self, handle, move |state| state.teleport_target(target));
+    }
+
+    fn apply_impulse<T: AnimValue>(&mut self, handle: AnimHandle<T>, impulse: T) {
+        modify_state(self, handle, move |state| state.apply_impulse(impulse));
+    }
+
+    fn apply_force<T: AnimValue>(&mut self, handle: AnimHandle<T>, force: T) {
+        modify_state(self, handle, move |state| state.add_force(force));
+    }
+
+    fn add_trauma(&mut self, entity: Entity, amount: f32) {
+        self.entity(entity)
+            .entry::<CameraShake>()
+            .and_modify(move |mut shake| shake.add_trauma(amount));
+    }
+}
+
+fn modify_state<T: AnimValue>(
+    commands: &mut Commands,
+    handle: AnimHandle<T>,
+    f: impl FnOnce(&mut DynamicsState<T>) + Send + Sync + 'static,
+) {
+    commands
+        .entity(handle.entity)
+        .entry::<DynamicsState<T>>()
+        .and_modify(move |mut state| f(&mut state));
+}
+
+fn modify_transform_state(
+    commands: &mut Commands,
+    handle: AnimHandle<Transform>,
+    f: impl FnOnce(&mut TransformDynamicsState) + Send + Sync + 'static,
+) {
+    commands
+        .entity(handle.entity)
+        .entry::<TransformDynamicsState>()
+        .and_modify(move |mut state| f(&mut state));
 }
 
 pub trait DynamicsEntityCommandsExt {
@@ -52,6 +350,31 @@ pub trait DynamicsEntityCommandsExt {
         &mut self,
         handle: AnimHandle<P::Property>,
     ) -> &mut Self;
+
+    fn animate_world<P: WorldSpaceProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self;
+
+    fn animate_computed<P: ComputedProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self;
+
+    fn animate_transform(&mut self, handle: AnimHandle<Transform>) -> &mut Self;
+
+    /// Binds `handle` to the field at a reflect path such as `"Transform.translation.y"`,
+    /// see [`PathDynamics`].
+    fn animate_path<T: AnimValue>(
+        &mut self,
+        path: impl Into<String>,
+        handle: AnimHandle<T>,
+    ) -> &mut Self;
+
+    fn animate_material<P: MaterialProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self;
 }
 
 impl<'a> DynamicsEntityCommandsExt for EntityCommands<'a> {
@@ -60,8 +383,54 @@ impl<'a> DynamicsEntityCommandsExt for EntityCommands<'a> {
         handle: AnimHandle<P::Property>,
     ) -> &mut Self {
         self.entry::<Dynamics>()
-            .or_insert(Dynamics::new())
+            .or_default()
             .and_modify(move |mut d| d.add::<P>(handle));
         self
     }
+
+    fn animate_world<P: WorldSpaceProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self {
+        self.entry::<Dynamics>()
+            .or_default()
+            .and_modify(move |mut d| d.add_world::<P>(handle));
+        self
+    }
+
+    fn animate_transform(&mut self, handle: AnimHandle<Transform>) -> &mut Self {
+        self.entry::<Dynamics>()
+            .or_default()
+            .and_modify(move |mut d| d.add_transform(handle));
+        self
+    }
+
+    fn animate_path<T: AnimValue>(
+        &mut self,
+        path: impl Into<String>,
+        handle: AnimHandle<T>,
+    ) -> &mut Self {
+        let path = path.into();
+        self.entry::<PathDynamics>()
+            .or_default()
+            .and_modify(move |mut d| d.add(path, handle));
+        self
+    }
+
+    fn animate_computed<P: ComputedProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self {
+        self.entry::<Dynamics>()
+            .or_default()
+            .and_modify(move |mut d| d.add_computed::<P>(handle));
+        self
+    }
+
+    fn animate_material<P: MaterialProperty>(
+        &mut self,
+        handle: AnimHandle<P::Property>,
+    ) -> &mut Self {
+        self.insert(MaterialDynamics::<P>::new(handle))
+    }
 }
@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::f32::consts::{LN_2, PI};
+
+use bevy_math::ops;
+use bevy_reflect::Reflect;
+
+use crate::{constraint::norm, solver::Solver, AnimValue};
+
+// Filters usable as solvers for any `AnimValue`, once registered with `init_solver`.
+
+/// Moves towards the target by a fixed fraction per unit of time, halving the remaining
+/// distance every `half_life` seconds.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct ExponentialSmoothing {
+    pub half_life: f32,
+}
+
+impl ExponentialSmoothing {
+    pub fn new(half_life: f32) -> Self {
+        Self { half_life }
+    }
+}
+
+impl<T: AnimValue> Solver<T> for ExponentialSmoothing {
+    type Params = Self;
+    type State = ();
+
+    fn tick(
+        params: &Self,
+        _state: &mut (),
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        _d_target: T,
+    ) {
+        let t = 1.0 - ops::exp(-LN_2 * dt / params.half_life);
+        let step = target.difference(*value) * t;
+        *value = *value + step;
+        *velocity = step / dt;
+    }
+}
+
+/// A critically damped spring reaching the target in roughly `smooth_time` seconds without
+/// overshooting, in the style of Unity's `SmoothDamp`.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct SmoothDamp {
+    pub smooth_time: f32,
+}
+
+impl SmoothDamp {
+    pub fn new(smooth_time: f32) -> Self {
+        Self { smooth_time }
+    }
+}
+
+impl<T: AnimValue> Solver<T> for SmoothDamp {
+    type Params = Self;
+    type State = ();
+
+    fn tick(
+        params: &Self,
+        _state: &mut (),
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        _d_target: T,
+    ) {
+        let omega = 2.0 / params.smooth_time.max(1e-4);
+        let x = omega * dt;
+        let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+        let change = value.difference(target);
+        let temp = (*velocity + change * omega) * dt;
+        let mut next_velocity = (*velocity - temp * omega) * decay;
+        let mut next_value = *value - change + (change + temp) * decay;
+
+        //don't overshoot the target
+        if dot(target.difference(*value), next_value.difference(target)) > 0.0 {
+            next_value = *value - change;
+            next_velocity = T::ZERO;
+        }
+
+        *value = next_value;
+        *velocity = next_velocity;
+    }
+}
+
+/// The one euro filter (Casiez et al. 2012), an adaptive low-pass filter for noisy input:
+/// slow movements are smoothed heavily to remove jitter, fast ones lightly to reduce lag.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct OneEuroFilter {
+    /// Cutoff frequency at rest, in Hz. Lower values remove more jitter.
+    pub min_cutoff: f32,
+    /// How quickly the cutoff grows with speed. Higher values reduce lag.
+    pub beta: f32,
+    /// Cutoff frequency for the speed estimate, in Hz.
+    pub d_cutoff: f32,
+}
+
+impl OneEuroFilter {
+    pub fn new(min_cutoff: f32, beta: f32) -> Self {
+        Self {
+            min_cutoff,
+            beta,
+            d_cutoff: 1.0,
+        }
+    }
+}
+
+impl<T: AnimValue> Solver<T> for OneEuroFilter {
+    type Params = Self;
+    type State = T;
+
+    fn tick(
+        params: &Self,
+        d_filtered: &mut T,
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        d_target: T,
+    ) {
+        *d_filtered =
+            *d_filtered + (d_target - *d_filtered) * smoothing_factor(params.d_cutoff, dt);
+        let cutoff = params.min_cutoff + params.beta * norm(*d_filtered);
+
+        let step = target.difference(*value) * smoothing_factor(cutoff, dt);
+        *value = *value + step;
+        *velocity = step / dt;
+    }
+}
+
+/// A first-order RC low-pass filter with the given cutoff frequency in Hz.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct LowPass {
+    pub cutoff: f32,
+}
+
+impl LowPass {
+    pub fn new(cutoff: f32) -> Self {
+        Self { cutoff }
+    }
+}
+
+impl<T: AnimValue> Solver<T> for LowPass {
+    type Params = Self;
+    type State = ();
+
+    fn tick(
+        params: &Self,
+        _state: &mut (),
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        _d_target: T,
+    ) {
+        let step = target.difference(*value) * smoothing_factor(params.cutoff, dt);
+        *value = *value + step;
+        *velocity = step / dt;
+    }
+}
+
+/// The average of the target over the last `window` seconds, weighted by frame time so it
+/// doesn't depend on the frame rate. A window that isn't positive follows the target exactly.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct MovingAverage {
+    pub window: f32,
+}
+
+impl MovingAverage {
+    pub fn new(window: f32) -> Self {
+        Self { window }
+    }
+}
+
+impl<T: AnimValue> Solver<T> for MovingAverage {
+    type Params = Self;
+    type State = VecDeque<(T, f32)>;
+
+    fn tick(
+        params: &Self,
+        samples: &mut VecDeque<(T, f32)>,
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        _d_target: T,
+    ) {
+        //an empty window has nothing to average
+        if params.window.is_nan() || params.window <= 0.0 {
+            samples.clear();
+            let step = target.difference(*value);
+            *value = target;
+            *velocity = step / dt;
+            return;
+        }
+
+        samples.push_back((target, dt));
+        let mut duration: f32 = samples.iter().map(|&(_, dt)| dt).sum();
+        while samples.len() > 1 && duration - samples[0].1 >= params.window {
+            duration -= samples[0].1;
+            samples.pop_front();
+        }
+
+        //averaged relative to the current value, so periodic values average correctly
+        let mut total = 0.0;
+        let mut sum = T::ZERO;
+        for &(sample, weight) in samples.iter().rev() {
+            let weight = weight.min(params.window - total);
+            if weight <= 0.0 {
+                break;
+            }
+            sum = sum + sample.difference(*value) * weight;
+            total += weight;
+        }
+        let step = sum / total;
+        *value = *value + step;
+        *velocity = step / dt;
+    }
+}
+
+// blend factor of a one-pole low-pass filter with the given cutoff frequency
+fn smoothing_factor(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (2.0 * PI * cutoff);
+    1.0 / (1.0 + tau / dt)
+}
+
+fn dot<T: AnimValue>(a: T, b: T) -> f32 {
+    (0..T::LANES).map(|i| a.lane(i) * b.lane(i)).sum()
+}
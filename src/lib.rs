@@ -1,34 +1,217 @@
-use bevy_app::{App, Plugin, PreUpdate};
+use bevy_app::{App, FixedPreUpdate, Plugin, PreUpdate};
 use bevy_color::{Laba, LinearRgba, Oklaba, Srgba, Xyza};
+use bevy_ecs::batching::BatchingStrategy;
+use bevy_ecs::prelude::any_with_component;
 use bevy_ecs::prelude::Resource;
-use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs};
+use bevy_ecs::schedule::{
+    InternedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel,
+};
+use bevy_hierarchy::Parent;
 use bevy_math::{Vec2, Vec3, Vec3A, Vec4, VectorSpace};
-use component::apply_dynamics;
-use state::{non_zero_delta, DynamicsSet, TickMode};
+use bevy_reflect::{FromReflect, Reflectable};
+use bevy_transform::components::GlobalTransform;
+use color::PolarColor;
+use component::{apply_dynamics, resolve_bindings, Dynamics};
+use path::{apply_path_dynamics, PathDynamics};
+use shake::CameraShakePlugin;
+use state::{
+    non_zero_delta, DynamicsSet, TickAnalytic, TickMode, TickPoleMatching, TickSimple, TickStable,
+};
+use transform::TransformDynamicsPlugin;
+use wrapping::Angle;
 
+pub mod analysis;
+#[cfg(feature = "simd")]
+pub mod batch;
+pub mod color;
 pub mod component;
+pub mod constraint;
 mod ext;
+pub mod filter;
+pub mod path;
+pub mod pid;
 pub mod props;
+pub mod shake;
+pub mod snapshot;
+pub mod solver;
+pub mod source;
 pub mod state;
+pub mod transform;
+pub mod wrapping;
 
 pub use ext::*;
 
-pub trait AnimValue: VectorSpace + Send + Sync + 'static {}
-impl<T: VectorSpace + Send + Sync + 'static> AnimValue for T {}
+pub trait AnimValue: VectorSpace + FromReflect + Reflectable + Send + Sync + 'static {
+    /// The number of scalar components, each addressed by [`AnimValue::lane`].
+    const LANES: usize;
+
+    fn lane(&self, index: usize) -> f32;
+    fn lane_mut(&mut self, index: usize) -> &mut f32;
+
+    /// The signed difference `self - other`, used for the error and target velocity of the
+    /// dynamics. Periodic values take the shortest way around instead.
+    fn difference(self, other: Self) -> Self {
+        self - other
+    }
+
+    /// Multiplies each lane by the matching lane of `other`.
+    fn mul_lanes(mut self, other: Self) -> Self {
+        for i in 0..Self::LANES {
+            *self.lane_mut(i) *= other.lane(i);
+        }
+        self
+    }
+
+    /// Divides each lane by the matching lane of `other`.
+    fn div_lanes(mut self, other: Self) -> Self {
+        for i in 0..Self::LANES {
+            *self.lane_mut(i) /= other.lane(i);
+        }
+        self
+    }
+}
+
+impl AnimValue for f32 {
+    const LANES: usize = 1;
+
+    fn lane(&self, index: usize) -> f32 {
+        assert_eq!(index, 0, "lane index out of bounds");
+        *self
+    }
+
+    fn lane_mut(&mut self, index: usize) -> &mut f32 {
+        assert_eq!(index, 0, "lane index out of bounds");
+        self
+    }
+}
+
+macro_rules! impl_vec_anim_value {
+    ($($ty: ident: $lanes: literal),*) => {
+        $(
+            impl AnimValue for $ty {
+                const LANES: usize = $lanes;
+
+                fn lane(&self, index: usize) -> f32 {
+                    self[index]
+                }
+
+                fn lane_mut(&mut self, index: usize) -> &mut f32 {
+                    &mut self[index]
+                }
+
+                fn mul_lanes(self, other: Self) -> Self {
+                    self * other
+                }
+
+                fn div_lanes(self, other: Self) -> Self {
+                    self / other
+                }
+            }
+        )*
+    };
+}
+
+impl_vec_anim_value!(Vec2: 2, Vec3: 3, Vec3A: 3, Vec4: 4);
+
+/// Implements [`AnimValue`] for a struct of `f32` fields, listed in lane order, e.g.
+/// `impl_fields_anim_value!(Stretch { 0 => width, 1 => height })`.
+#[macro_export]
+macro_rules! impl_fields_anim_value {
+    ($ty: ty { $($index: literal => $field: ident),* }) => {
+        impl $crate::AnimValue for $ty {
+            const LANES: usize = [$($index),*].len();
+
+            fn lane(&self, index: usize) -> f32 {
+                match index {
+                    $($index => self.$field,)*
+                    _ => panic!("lane index out of bounds"),
+                }
+            }
+
+            fn lane_mut(&mut self, index: usize) -> &mut f32 {
+                match index {
+                    $($index => &mut self.$field,)*
+                    _ => panic!("lane index out of bounds"),
+                }
+            }
+        }
+    };
+}
+
+impl_fields_anim_value!(Xyza { 0 => x, 1 => y, 2 => z, 3 => alpha });
+impl_fields_anim_value!(Srgba { 0 => red, 1 => green, 2 => blue, 3 => alpha });
+impl_fields_anim_value!(Oklaba { 0 => lightness, 1 => a, 2 => b, 3 => alpha });
+impl_fields_anim_value!(LinearRgba { 0 => red, 1 => green, 2 => blue, 3 => alpha });
+impl_fields_anim_value!(Laba { 0 => lightness, 1 => a, 2 => b, 3 => alpha });
 
 #[derive(Resource)]
 pub struct DefaultTickMode(pub TickMode);
 
-#[derive(Default)]
+/// Batch size of the parallel tick and apply systems. `None` lets Bevy split the work
+/// evenly between threads.
+#[derive(Resource, Default, Copy, Clone, Debug)]
+pub struct ParallelBatchSize(pub Option<usize>);
+
+impl ParallelBatchSize {
+    pub(crate) fn strategy(&self) -> BatchingStrategy {
+        match self.0 {
+            Some(batch_size) => BatchingStrategy::fixed(batch_size),
+            None => BatchingStrategy::new(),
+        }
+    }
+}
+
+/// The schedule the dynamics run in, from [`DynamicsPlugin::schedule`].
+#[derive(Resource, Copy, Clone, Debug)]
+pub struct DynamicsSchedule(pub InternedScheduleLabel);
+
+impl DynamicsSchedule {
+    // panics rather than falling back to a schedule `DynamicsSet` isn't configured in, where
+    // the systems would run unordered and without the `non_zero_delta` condition
+    pub(crate) fn of(app: &App) -> InternedScheduleLabel {
+        app.world()
+            .get_resource::<Self>()
+            .expect("`DynamicsPlugin` must be added before initializing animatable types, solvers or material properties")
+            .0
+    }
+}
+
 pub struct DynamicsPlugin {
     pub default_tick_mode: TickMode,
+    pub batch_size: Option<usize>,
+    /// The schedule sources are read, handles ticked and properties written in.
+    pub schedule: InternedScheduleLabel,
+}
+
+impl DynamicsPlugin {
+    /// Runs the dynamics in [`FixedPreUpdate`], so every tick uses the same `dt`. Together
+    /// with the `libm` feature, which makes the math behind the solvers platform independent,
+    /// this gives bit-identical results across machines for the same inputs.
+    pub fn fixed_timestep() -> Self {
+        Self {
+            schedule: FixedPreUpdate.intern(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for DynamicsPlugin {
+    fn default() -> Self {
+        Self {
+            default_tick_mode: Default::default(),
+            batch_size: None,
+            schedule: PreUpdate.intern(),
+        }
+    }
 }
 
 impl Plugin for DynamicsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(DefaultTickMode(self.default_tick_mode))
+            .insert_resource(ParallelBatchSize(self.batch_size))
+            .insert_resource(DynamicsSchedule(self.schedule))
             .configure_sets(
-                PreUpdate,
+                self.schedule,
                 (
                     DynamicsSet::All.run_if(non_zero_delta),
                     DynamicsSet::Read.in_set(DynamicsSet::All),
@@ -37,7 +220,33 @@ impl Plugin for DynamicsPlugin {
                     (DynamicsSet::Read, DynamicsSet::Tick, DynamicsSet::Write).chain(),
                 ),
             )
-            .add_systems(PreUpdate, apply_dynamics.in_set(DynamicsSet::Write));
+            .add_systems(
+                self.schedule,
+                (
+                    resolve_bindings,
+                    apply_dynamics,
+                    //exclusive, so only run when there's something to write
+                    apply_path_dynamics.run_if(any_with_component::<PathDynamics>),
+                )
+                    .chain()
+                    .in_set(DynamicsSet::Write),
+            )
+            .add_plugins((TransformDynamicsPlugin, CameraShakePlugin))
+            .register_type::<Dynamics>()
+            .register_type::<PathDynamics>()
+            .register_type::<TickSimple>()
+            .register_type::<TickStable>()
+            .register_type::<TickPoleMatching>()
+            .register_type::<TickAnalytic>();
+
+        props::register_properties(app);
+
+        //`EntityMutExcept` only excludes components registered when `apply_dynamics` is
+        //initialized, which may be before any of them is spawned
+        let world = app.world_mut();
+        world.register_component::<Dynamics>();
+        world.register_component::<Parent>();
+        world.register_component::<GlobalTransform>();
 
         app.init_animatable_type::<f32>()
             .init_animatable_type::<Vec2>()
@@ -48,6 +257,18 @@ impl Plugin for DynamicsPlugin {
             .init_animatable_type::<Srgba>()
             .init_animatable_type::<Oklaba>()
             .init_animatable_type::<LinearRgba>()
-            .init_animatable_type::<Laba>();
+            .init_animatable_type::<Laba>()
+            .init_animatable_type::<PolarColor>()
+            .init_animatable_type::<Angle>();
+
+        #[cfg(feature = "simd")]
+        app.add_plugins(batch::DynamicsBatchPlugin);
+
+        #[cfg(feature = "bevy_sprite")]
+        app.init_material_property::<props::ColorMaterialColorProperty>();
+
+        #[cfg(feature = "bevy_pbr")]
+        app.init_material_property::<props::StandardMaterialBaseColorProperty>()
+            .init_material_property::<props::StandardMaterialEmissiveProperty>();
     }
 }
@@ -0,0 +1,282 @@
+use std::{any::TypeId, fmt};
+
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    entity::{Entity, EntityMapper, MapEntities},
+    prelude::Local,
+    query::QueryState,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    world::{Ref, World},
+};
+use bevy_reflect::{
+    std_traits::ReflectDefault, FromType, ParsedPath, Reflect, ReflectPath, TypeRegistry,
+};
+
+use crate::{
+    state::{AnimHandle, DynamicsState},
+    AnimValue,
+};
+
+/// Fields of an entity's components written from handles, addressed by reflect paths such as
+/// `"Transform.translation.y"`, so bindings can be authored in scenes and other data.
+///
+/// The first segment names a reflected component by its type path or short type path, and
+/// the rest is a [`ParsedPath`] into it. A binding is resolved once the entity has the
+/// component, and the field has to be of the type `T` of its handle, which has to be
+/// initialized with [`init_animatable_type`](crate::DynamicsAppExt::init_animatable_type).
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default, MapEntities)]
+pub struct PathDynamics {
+    bindings: Vec<PathBinding>,
+}
+
+/// A field of an entity written from a handle, see [`PathDynamics`].
+#[derive(Clone, Reflect)]
+pub struct PathBinding {
+    path: String,
+    source: Entity,
+    // `None` until the entity has the component and the source its state, cleared when the
+    // bindings change
+    #[reflect(ignore)]
+    resolved: Option<Result<ResolvedPath, PathBindingError>>,
+}
+
+impl PathBinding {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The entity of the handle the field is written from.
+    pub fn source(&self) -> Entity {
+        self.source
+    }
+
+    /// Why the binding couldn't be resolved, if it couldn't.
+    pub fn error(&self) -> Option<&PathBindingError> {
+        self.resolved.as_ref()?.as_ref().err()
+    }
+}
+
+#[derive(Clone)]
+struct ResolvedPath {
+    component: ReflectComponent,
+    field: ParsedPath,
+    write: WriteField,
+}
+
+impl PathDynamics {
+    pub fn bindings(&self) -> &[PathBinding] {
+        &self.bindings
+    }
+
+    pub fn add<T: AnimValue>(&mut self, path: impl Into<String>, source: AnimHandle<T>) {
+        let binding = PathBinding {
+            path: path.into(),
+            source: source.entity,
+            resolved: None,
+        };
+        match self
+            .bindings
+            .iter_mut()
+            .find(|existing| existing.path == binding.path)
+        {
+            Some(existing) => *existing = binding,
+            None => self.bindings.push(binding),
+        }
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        self.bindings.retain(|binding| binding.path != path);
+    }
+}
+
+impl MapEntities for PathDynamics {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for binding in &mut self.bindings {
+            binding.source = entity_mapper.map_entity(binding.source);
+        }
+    }
+}
+
+/// Why a [`PathBinding`] couldn't be resolved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathBindingError {
+    /// The first segment of the path isn't a registered, reflected component.
+    UnknownComponent(String),
+    /// The rest of the path doesn't lead to a field of the component.
+    InvalidPath(String),
+    /// The field has a type that wasn't initialized with
+    /// [`init_animatable_type`](crate::DynamicsAppExt::init_animatable_type).
+    NotAnimatable(String),
+    /// The handle doesn't animate a value of the type of the field. Unlike the other errors,
+    /// this is checked again every frame, as the source may be given a state later.
+    TypeMismatch(String),
+}
+
+impl fmt::Display for PathBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownComponent(name) => write!(f, "`{name}` isn't a reflected component"),
+            Self::InvalidPath(error) => write!(f, "invalid path: {error}"),
+            Self::NotAnimatable(ty) => write!(f, "`{ty}` isn't an animatable type"),
+            Self::TypeMismatch(ty) => write!(f, "the handle doesn't animate a `{ty}`"),
+        }
+    }
+}
+
+impl std::error::Error for PathBindingError {}
+
+// writes the value of the handle in the first entity to a field of a component of the second
+type WriteField = fn(&mut World, Entity, Entity, &ReflectComponent, &ParsedPath);
+
+/// Type data of the types initialized with
+/// [`init_animatable_type`](crate::DynamicsAppExt::init_animatable_type), letting
+/// [`PathDynamics`] write the value of their handles to a reflected field.
+#[derive(Clone)]
+pub struct ReflectAnimValue {
+    state: TypeId,
+    write: WriteField,
+}
+
+impl<T: AnimValue> FromType<T> for ReflectAnimValue {
+    fn from_type() -> Self {
+        Self {
+            state: TypeId::of::<DynamicsState<T>>(),
+            write: write_field::<T>,
+        }
+    }
+}
+
+fn write_field<T: AnimValue>(
+    world: &mut World,
+    source: Entity,
+    destination: Entity,
+    component: &ReflectComponent,
+    field: &ParsedPath,
+) {
+    let Some(value) = world
+        .get::<DynamicsState<T>>(source)
+        .map(DynamicsState::value)
+    else {
+        return;
+    };
+    let Ok(mut destination) = world.get_entity_mut(destination) else {
+        return;
+    };
+    let Some(mut component) = component.reflect_mut(&mut destination) else {
+        return;
+    };
+    let Ok(field) = field.reflect_element_mut(component.as_partial_reflect_mut()) else {
+        return;
+    };
+    if let Some(field) = field.try_downcast_mut::<T>() {
+        *field = value;
+    }
+}
+
+fn resolve(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    binding: &PathBinding,
+) -> Option<Result<ResolvedPath, PathBindingError>> {
+    let (name, field) = match binding.path.find(['.', '#', '[']) {
+        Some(index) => binding.path.split_at(index),
+        None => (binding.path.as_str(), ""),
+    };
+    let Some(component) = registry
+        .get_with_type_path(name)
+        .or_else(|| registry.get_with_short_type_path(name))
+        .and_then(|registration| registration.data::<ReflectComponent>())
+    else {
+        return Some(Err(PathBindingError::UnknownComponent(name.to_owned())));
+    };
+    let field = match ParsedPath::parse(field) {
+        Ok(field) => field,
+        Err(error) => return Some(Err(PathBindingError::InvalidPath(error.to_string()))),
+    };
+
+    let value = component.reflect(world.get_entity(entity).ok()?)?;
+    let value = match (&field).reflect_element(value.as_partial_reflect()) {
+        Ok(value) => value,
+        Err(error) => return Some(Err(PathBindingError::InvalidPath(error.to_string()))),
+    };
+    let Some(ty) = value.get_represented_type_info() else {
+        return Some(Err(PathBindingError::NotAnimatable(
+            value.reflect_type_path().to_owned(),
+        )));
+    };
+    let Some(anim_value) = registry.get_type_data::<ReflectAnimValue>(ty.type_id()) else {
+        return Some(Err(PathBindingError::NotAnimatable(
+            ty.type_path().to_owned(),
+        )));
+    };
+    //a source that doesn't exist yet, e.g. while a scene is being spawned, is retried
+    let source = world.get_entity(binding.source).ok()?;
+    if !source.contains_type_id(anim_value.state) {
+        return Some(Err(PathBindingError::TypeMismatch(
+            ty.type_path().to_owned(),
+        )));
+    }
+
+    Some(Ok(ResolvedPath {
+        component: component.clone(),
+        field,
+        write: anim_value.write,
+    }))
+}
+
+pub(crate) fn apply_path_dynamics(
+    world: &mut World,
+    query: &mut QueryState<(Entity, Ref<PathDynamics>)>,
+    mut entities: Local<Vec<(Entity, bool)>>,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    entities.extend(
+        query
+            .iter(world)
+            .map(|(entity, dynamics)| (entity, dynamics.is_changed())),
+    );
+    for (entity, changed) in entities.drain(..) {
+        //take the bindings out while writing, so the world can be borrowed mutably
+        let mut bindings = std::mem::take(
+            &mut world
+                .get_mut::<PathDynamics>(entity)
+                .unwrap()
+                .bypass_change_detection()
+                .bindings,
+        );
+
+        for binding in &mut bindings {
+            //a changed path or source may resolve differently, and the source may be given the
+            //state of the field's type later, so only errors of the path itself are kept
+            if changed
+                || matches!(
+                    binding.resolved,
+                    Some(Err(PathBindingError::TypeMismatch(_)))
+                )
+            {
+                binding.resolved = None;
+            }
+            if binding.resolved.is_none() {
+                binding.resolved = resolve(world, &registry.read(), entity, binding);
+            }
+            if let Some(Ok(resolved)) = &binding.resolved {
+                (resolved.write)(
+                    world,
+                    binding.source,
+                    entity,
+                    &resolved.component,
+                    &resolved.field,
+                );
+            }
+        }
+
+        world
+            .get_mut::<PathDynamics>(entity)
+            .unwrap()
+            .bypass_change_detection()
+            .bindings = bindings;
+    }
+}
@@ -0,0 +1,83 @@
+use bevy_reflect::Reflect;
+
+use crate::{solver::Solver, AnimValue};
+
+/// What the derivative term of a [`PidController`] acts on.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+pub enum DerivativeMode {
+    /// The rate of change of the error, including the velocity of the target.
+    #[default]
+    Error,
+    /// Only the velocity of the value, so jumps in the target don't kick the output.
+    Measurement,
+}
+
+/// A PID controller, accelerating the value by `kp * e + ki * ∫e + kd * de/dt` for the error
+/// `e` between the target and the value.
+#[derive(Copy, Clone, Debug, Reflect)]
+pub struct PidController {
+    pub kp: f32,
+    pub ki: f32,
+    pub kd: f32,
+    /// Clamps each component of the accumulated error, preventing windup while the
+    /// controller can't keep up with the target.
+    pub integral_limit: Option<f32>,
+    pub derivative: DerivativeMode,
+}
+
+impl PidController {
+    pub fn new(kp: f32, ki: f32, kd: f32) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            integral_limit: None,
+            derivative: DerivativeMode::Error,
+        }
+    }
+
+    pub fn with_integral_limit(mut self, integral_limit: f32) -> Self {
+        self.integral_limit = Some(integral_limit);
+        self
+    }
+
+    pub fn with_derivative(mut self, derivative: DerivativeMode) -> Self {
+        self.derivative = derivative;
+        self
+    }
+}
+
+impl<T: AnimValue> Solver<T> for PidController {
+    type Params = Self;
+    // the accumulated error
+    type State = T;
+
+    fn tick(
+        params: &Self,
+        integral: &mut T,
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        d_target: T,
+    ) {
+        let error = target.difference(*value);
+
+        *integral = *integral + error * dt;
+        if let Some(limit) = params.integral_limit {
+            for i in 0..T::LANES {
+                let lane = integral.lane_mut(i);
+                *lane = lane.min(limit.abs()).max(-limit.abs());
+            }
+        }
+
+        let d_error = match params.derivative {
+            DerivativeMode::Error => d_target - *velocity,
+            DerivativeMode::Measurement => T::ZERO - *velocity,
+        };
+
+        let acceleration = error * params.kp + *integral * params.ki + d_error * params.kd;
+        *velocity = *velocity + acceleration * dt;
+        *value = *value + *velocity * dt;
+    }
+}
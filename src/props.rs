@@ -2,10 +2,40 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use bevy_animation::{animatable::Animatable, animation_curves::AnimatableProperty};
+use bevy_app::App;
+use bevy_asset::{Asset, AssetId};
 use bevy_ecs::component::Component;
-use bevy_math::{Vec3, VectorSpace};
-use bevy_reflect::{FromReflect, Reflect, Reflectable};
-use bevy_transform::components::Transform;
+use bevy_math::{Quat, Vec3, VectorSpace};
+use bevy_reflect::{FromReflect, Reflect, Reflectable, TypePath};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    component::{apply_transform, PropertyRegistry},
+    wrapping::Angle,
+    AnimValue, DynamicsAppExt,
+};
+
+#[cfg(feature = "bevy_pbr")]
+mod pbr;
+#[cfg(feature = "bevy_render")]
+mod render;
+#[cfg(feature = "bevy_sprite")]
+mod sprite;
+#[cfg(feature = "bevy_text")]
+mod text;
+#[cfg(feature = "bevy_ui")]
+mod ui;
+
+#[cfg(feature = "bevy_pbr")]
+pub use pbr::*;
+#[cfg(feature = "bevy_render")]
+pub use render::*;
+#[cfg(feature = "bevy_sprite")]
+pub use sprite::*;
+#[cfg(feature = "bevy_text")]
+pub use text::*;
+#[cfg(feature = "bevy_ui")]
+pub use ui::*;
 
 #[derive(Reflect)]
 pub struct IdProperty<C>(#[reflect(ignore)] PhantomData<C>);
@@ -47,5 +77,157 @@ macro_rules! prop {
     };
 }
 
+#[macro_export]
+macro_rules! try_prop {
+    ($pub:vis struct $name:ident, $component: ident, $prop: ident, $op: expr) => {
+        #[derive(Reflect, Debug)]
+        $pub struct $name;
+
+        impl AnimatableProperty for $name {
+            type Component = $component;
+            type Property = $prop;
+
+            fn get_mut(component: &mut Self::Component) -> Option<&mut Self::Property> {
+                let op: fn(&mut Self::Component) -> Option<&mut Self::Property> = $op;
+                (op)(component)
+            }
+        }
+    };
+}
+
 prop!(pub struct TranslationProperty, Transform, Vec3, |tf| &mut tf.translation);
+prop!(pub struct TranslationXProperty, Transform, f32, |tf| &mut tf.translation.x);
+prop!(pub struct TranslationYProperty, Transform, f32, |tf| &mut tf.translation.y);
+prop!(pub struct TranslationZProperty, Transform, f32, |tf| &mut tf.translation.z);
+
 prop!(pub struct ScaleProperty, Transform, Vec3, |tf| &mut tf.scale);
+prop!(pub struct ScaleXProperty, Transform, f32, |tf| &mut tf.scale.x);
+prop!(pub struct ScaleYProperty, Transform, f32, |tf| &mut tf.scale.y);
+prop!(pub struct ScaleZProperty, Transform, f32, |tf| &mut tf.scale.z);
+
+/// Writes translation, rotation and scale of a [`Transform`] from a single coupled
+/// transform handle, see [`DynamicsEntityCommandsExt::animate_transform`](crate::DynamicsEntityCommandsExt::animate_transform).
+#[derive(TypePath)]
+pub struct TransformProperty;
+
+/// A property animated in world space. The dynamics value is converted into the
+/// local space of the entity's parent before being written.
+///
+/// The parent's [`GlobalTransform`] is the one from the last transform propagation, which
+/// by default ran in `PostUpdate` of the previous frame, so a child of a parent moved in
+/// `Update` lags it by a frame. To write against the current parent, move it before
+/// [`DynamicsSet::Write`](crate::state::DynamicsSet::Write), and run
+/// `bevy_transform::systems::propagate_transforms` between the two.
+pub trait WorldSpaceProperty: TypePath + Send + Sync + 'static {
+    type Component: Component;
+    type Property: AnimValue;
+
+    fn set_world(component: &mut Self::Component, parent: &GlobalTransform, value: Self::Property);
+}
+
+#[derive(TypePath)]
+pub struct WorldTranslationProperty;
+
+impl WorldSpaceProperty for WorldTranslationProperty {
+    type Component = Transform;
+    type Property = Vec3;
+
+    fn set_world(component: &mut Transform, parent: &GlobalTransform, value: Vec3) {
+        component.translation = parent.affine().inverse().transform_point3(value);
+    }
+}
+
+/// The scale relative to the world, divided by the parent's scale per axis. A child of a
+/// rotated parent with a non-uniform scale inherits skew, which a [`Transform`] can't undo,
+/// so its world scale is only approximate.
+#[derive(TypePath)]
+pub struct WorldScaleProperty;
+
+impl WorldSpaceProperty for WorldScaleProperty {
+    type Component = Transform;
+    type Property = Vec3;
+
+    fn set_world(component: &mut Transform, parent: &GlobalTransform, value: Vec3) {
+        component.scale = value / parent.scale();
+    }
+}
+
+/// A property that isn't stored as a plain field, so the animated value is converted
+/// when it is written to the component.
+pub trait ComputedProperty: TypePath + Send + Sync + 'static {
+    type Component: Component;
+    type Property: AnimValue;
+
+    fn set(component: &mut Self::Component, value: Self::Property);
+}
+
+/// The rotation of a 2D transform around the z axis.
+#[derive(TypePath)]
+pub struct Rotation2dProperty;
+
+impl ComputedProperty for Rotation2dProperty {
+    type Component = Transform;
+    type Property = Angle;
+
+    fn set(component: &mut Transform, value: Angle) {
+        component.rotation = Quat::from_rotation_z(value.0);
+    }
+}
+
+/// A property of a material asset, reached through the handle component on the animated
+/// entity. Note that every entity sharing the material sees the animated value.
+pub trait MaterialProperty: TypePath + Send + Sync + 'static {
+    type Handle: Component;
+    type Material: Asset;
+    type Property: AnimValue;
+
+    fn material(handle: &Self::Handle) -> AssetId<Self::Material>;
+    fn get_mut(material: &mut Self::Material) -> Option<&mut Self::Property>;
+}
+
+// registers the built-in properties, so bindings to them can be resolved from a scene
+pub(crate) fn register_properties(app: &mut App) {
+    app.world_mut()
+        .get_resource_or_init::<PropertyRegistry>()
+        .register::<TransformProperty>(apply_transform);
+    app.register_property::<TranslationProperty>()
+        .register_property::<TranslationXProperty>()
+        .register_property::<TranslationYProperty>()
+        .register_property::<TranslationZProperty>()
+        .register_property::<ScaleProperty>()
+        .register_property::<ScaleXProperty>()
+        .register_property::<ScaleYProperty>()
+        .register_property::<ScaleZProperty>()
+        .register_world_property::<WorldTranslationProperty>()
+        .register_world_property::<WorldScaleProperty>()
+        .register_computed_property::<Rotation2dProperty>();
+
+    #[cfg(feature = "bevy_pbr")]
+    pbr::register_properties(app);
+    #[cfg(feature = "bevy_render")]
+    render::register_properties(app);
+    #[cfg(feature = "bevy_sprite")]
+    sprite::register_properties(app);
+    #[cfg(feature = "bevy_text")]
+    text::register_properties(app);
+    #[cfg(feature = "bevy_ui")]
+    ui::register_properties(app);
+}
+
+#[cfg(any(feature = "bevy_sprite", feature = "bevy_text", feature = "bevy_ui"))]
+fn color_alpha_mut(color: &mut bevy_color::Color) -> &mut f32 {
+    use bevy_color::Color;
+
+    match color {
+        Color::Srgba(c) => &mut c.alpha,
+        Color::LinearRgba(c) => &mut c.alpha,
+        Color::Hsla(c) => &mut c.alpha,
+        Color::Hsva(c) => &mut c.alpha,
+        Color::Hwba(c) => &mut c.alpha,
+        Color::Laba(c) => &mut c.alpha,
+        Color::Lcha(c) => &mut c.alpha,
+        Color::Oklaba(c) => &mut c.alpha,
+        Color::Oklcha(c) => &mut c.alpha,
+        Color::Xyza(c) => &mut c.alpha,
+    }
+}
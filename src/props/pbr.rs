@@ -0,0 +1,111 @@
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_app::App;
+use bevy_asset::AssetId;
+use bevy_color::{Color, LinearRgba, Srgba};
+use bevy_pbr::{DirectionalLight, MeshMaterial3d, PointLight, SpotLight, StandardMaterial};
+use bevy_reflect::{Reflect, TypePath};
+
+use crate::{color::ColorField, prop, try_prop, DynamicsAppExt};
+
+use super::MaterialProperty;
+
+prop!(pub struct PointLightIntensityProperty, PointLight, f32, |light| &mut light.intensity);
+prop!(pub struct SpotLightIntensityProperty, SpotLight, f32, |light| &mut light.intensity);
+prop!(pub struct DirectionalLightIlluminanceProperty, DirectionalLight, f32, |light| &mut light.illuminance);
+
+try_prop!(pub struct PointLightColorProperty, PointLight, Srgba, |light| match light.color {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+try_prop!(pub struct SpotLightColorProperty, SpotLight, Srgba, |light| match light.color {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+try_prop!(pub struct DirectionalLightColorProperty, DirectionalLight, Srgba, |light| match light.color {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+pub(super) fn register_properties(app: &mut App) {
+    app.register_property::<PointLightIntensityProperty>()
+        .register_property::<SpotLightIntensityProperty>()
+        .register_property::<DirectionalLightIlluminanceProperty>()
+        .register_property::<PointLightColorProperty>()
+        .register_property::<SpotLightColorProperty>()
+        .register_property::<DirectionalLightColorProperty>()
+        .register_color_field::<PointLightColorField>()
+        .register_color_field::<SpotLightColorField>()
+        .register_color_field::<DirectionalLightColorField>();
+}
+
+#[derive(TypePath)]
+pub struct StandardMaterialBaseColorProperty;
+
+impl MaterialProperty for StandardMaterialBaseColorProperty {
+    type Handle = MeshMaterial3d<StandardMaterial>;
+    type Material = StandardMaterial;
+    type Property = Srgba;
+
+    fn material(handle: &Self::Handle) -> AssetId<StandardMaterial> {
+        handle.0.id()
+    }
+
+    fn get_mut(material: &mut StandardMaterial) -> Option<&mut Srgba> {
+        match material.base_color {
+            Color::Srgba(ref mut srgba) => Some(srgba),
+            _ => None,
+        }
+    }
+}
+
+#[derive(TypePath)]
+pub struct StandardMaterialEmissiveProperty;
+
+impl MaterialProperty for StandardMaterialEmissiveProperty {
+    type Handle = MeshMaterial3d<StandardMaterial>;
+    type Material = StandardMaterial;
+    type Property = LinearRgba;
+
+    fn material(handle: &Self::Handle) -> AssetId<StandardMaterial> {
+        handle.0.id()
+    }
+
+    fn get_mut(material: &mut StandardMaterial) -> Option<&mut LinearRgba> {
+        Some(&mut material.emissive)
+    }
+}
+
+#[derive(TypePath)]
+pub struct PointLightColorField;
+
+impl ColorField for PointLightColorField {
+    type Component = PointLight;
+
+    fn color_mut(light: &mut PointLight) -> &mut Color {
+        &mut light.color
+    }
+}
+
+#[derive(TypePath)]
+pub struct SpotLightColorField;
+
+impl ColorField for SpotLightColorField {
+    type Component = SpotLight;
+
+    fn color_mut(light: &mut SpotLight) -> &mut Color {
+        &mut light.color
+    }
+}
+
+#[derive(TypePath)]
+pub struct DirectionalLightColorField;
+
+impl ColorField for DirectionalLightColorField {
+    type Component = DirectionalLight;
+
+    fn color_mut(light: &mut DirectionalLight) -> &mut Color {
+        &mut light.color
+    }
+}
@@ -0,0 +1,24 @@
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_app::App;
+use bevy_reflect::Reflect;
+use bevy_render::camera::{OrthographicProjection, Projection};
+
+use crate::{prop, try_prop, DynamicsAppExt};
+
+try_prop!(pub struct CameraFovProperty, Projection, f32, |projection| match projection {
+    Projection::Perspective(perspective) => Some(&mut perspective.fov),
+    _ => None,
+});
+
+try_prop!(pub struct ProjectionScaleProperty, Projection, f32, |projection| match projection {
+    Projection::Orthographic(orthographic) => Some(&mut orthographic.scale),
+    _ => None,
+});
+
+prop!(pub struct OrthographicScaleProperty, OrthographicProjection, f32, |orthographic| &mut orthographic.scale);
+
+pub(super) fn register_properties(app: &mut App) {
+    app.register_property::<CameraFovProperty>()
+        .register_property::<ProjectionScaleProperty>()
+        .register_property::<OrthographicScaleProperty>();
+}
@@ -0,0 +1,54 @@
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_app::App;
+use bevy_asset::AssetId;
+use bevy_color::{Color, Srgba};
+use bevy_reflect::{Reflect, TypePath};
+use bevy_sprite::{ColorMaterial, MeshMaterial2d, Sprite};
+
+use crate::{color::ColorField, try_prop, DynamicsAppExt};
+
+use super::{color_alpha_mut, MaterialProperty};
+
+try_prop!(pub struct SpriteColorProperty, Sprite, Srgba, |sprite| match sprite.color {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+try_prop!(pub struct SpriteAlphaProperty, Sprite, f32, |sprite| Some(color_alpha_mut(&mut sprite.color)));
+
+pub(super) fn register_properties(app: &mut App) {
+    app.register_property::<SpriteColorProperty>()
+        .register_property::<SpriteAlphaProperty>()
+        .register_color_field::<SpriteColorField>();
+}
+
+#[derive(TypePath)]
+pub struct ColorMaterialColorProperty;
+
+impl MaterialProperty for ColorMaterialColorProperty {
+    type Handle = MeshMaterial2d<ColorMaterial>;
+    type Material = ColorMaterial;
+    type Property = Srgba;
+
+    fn material(handle: &Self::Handle) -> AssetId<ColorMaterial> {
+        handle.0.id()
+    }
+
+    fn get_mut(material: &mut ColorMaterial) -> Option<&mut Srgba> {
+        match material.color {
+            Color::Srgba(ref mut srgba) => Some(srgba),
+            _ => None,
+        }
+    }
+}
+
+#[derive(TypePath)]
+pub struct SpriteColorField;
+
+impl ColorField for SpriteColorField {
+    type Component = Sprite;
+
+    fn color_mut(sprite: &mut Sprite) -> &mut Color {
+        &mut sprite.color
+    }
+}
@@ -0,0 +1,33 @@
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_app::App;
+use bevy_color::{Color, Srgba};
+use bevy_reflect::{Reflect, TypePath};
+use bevy_text::TextColor;
+
+use crate::{color::ColorField, try_prop, DynamicsAppExt};
+
+use super::color_alpha_mut;
+
+try_prop!(pub struct TextColorProperty, TextColor, Srgba, |text| match text.0 {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+try_prop!(pub struct TextAlphaProperty, TextColor, f32, |text| Some(color_alpha_mut(&mut text.0)));
+
+pub(super) fn register_properties(app: &mut App) {
+    app.register_property::<TextColorProperty>()
+        .register_property::<TextAlphaProperty>()
+        .register_color_field::<TextColorField>();
+}
+
+#[derive(TypePath)]
+pub struct TextColorField;
+
+impl ColorField for TextColorField {
+    type Component = TextColor;
+
+    fn color_mut(text: &mut TextColor) -> &mut Color {
+        &mut text.0
+    }
+}
@@ -0,0 +1,59 @@
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_app::App;
+use bevy_color::{Color, Srgba};
+use bevy_reflect::{Reflect, TypePath};
+use bevy_ui::{BackgroundColor, Node, Val};
+
+use crate::{color::ColorField, try_prop, DynamicsAppExt};
+
+use super::color_alpha_mut;
+
+// animates the numeric part of a `Val` in whichever unit it is set, `Val::Auto` is left alone
+fn val_mut(val: &mut Val) -> Option<&mut f32> {
+    match val {
+        Val::Auto => None,
+        Val::Px(value)
+        | Val::Percent(value)
+        | Val::Vw(value)
+        | Val::Vh(value)
+        | Val::VMin(value)
+        | Val::VMax(value) => Some(value),
+    }
+}
+
+try_prop!(pub struct NodeWidthProperty, Node, f32, |node| val_mut(&mut node.width));
+try_prop!(pub struct NodeHeightProperty, Node, f32, |node| val_mut(&mut node.height));
+try_prop!(pub struct NodeLeftProperty, Node, f32, |node| val_mut(&mut node.left));
+try_prop!(pub struct NodeRightProperty, Node, f32, |node| val_mut(&mut node.right));
+try_prop!(pub struct NodeTopProperty, Node, f32, |node| val_mut(&mut node.top));
+try_prop!(pub struct NodeBottomProperty, Node, f32, |node| val_mut(&mut node.bottom));
+
+try_prop!(pub struct BackgroundColorProperty, BackgroundColor, Srgba, |background| match background.0 {
+    Color::Srgba(ref mut srgba) => Some(srgba),
+    _ => None,
+});
+
+try_prop!(pub struct BackgroundAlphaProperty, BackgroundColor, f32, |background| Some(color_alpha_mut(&mut background.0)));
+
+pub(super) fn register_properties(app: &mut App) {
+    app.register_property::<NodeWidthProperty>()
+        .register_property::<NodeHeightProperty>()
+        .register_property::<NodeLeftProperty>()
+        .register_property::<NodeRightProperty>()
+        .register_property::<NodeTopProperty>()
+        .register_property::<NodeBottomProperty>()
+        .register_property::<BackgroundColorProperty>()
+        .register_property::<BackgroundAlphaProperty>()
+        .register_color_field::<BackgroundColorField>();
+}
+
+#[derive(TypePath)]
+pub struct BackgroundColorField;
+
+impl ColorField for BackgroundColorField {
+    type Component = BackgroundColor;
+
+    fn color_mut(background: &mut BackgroundColor) -> &mut Color {
+        &mut background.0
+    }
+}
@@ -0,0 +1,182 @@
+use bevy_app::{App, First, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::{ops::FloatPow, FloatExt, Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::{components::Transform, TransformSystem};
+
+use crate::{
+    state::{DynamicsParams, DynamicsSet, DynamicsState, TickMode},
+    DynamicsSchedule, ParallelBatchSize,
+};
+
+/// Trauma-based shake for a camera, or any other entity with a [`Transform`].
+///
+/// Trauma in `[0, 1]` decays over time and scales noise-driven targets for a translation and
+/// a rotation offset, which follow them through springs. The offset is added to the
+/// transform just before transform propagation and removed again at the start of the next
+/// frame, so gameplay systems only ever see the unshaken transform.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct CameraShake {
+    pub trauma: f32,
+    /// Trauma lost per second.
+    pub decay: f32,
+    pub max_translation: Vec3,
+    /// Maximum rotation offset around each local axis, in radians.
+    pub max_rotation: Vec3,
+    /// Frequency of the noise, in samples per second.
+    pub frequency: f32,
+    pub tick_mode: TickMode,
+
+    time: f32,
+    translation: DynamicsState<Vec3>,
+    rotation: DynamicsState<Vec3>,
+    applied: Option<(Vec3, Quat)>,
+}
+
+impl CameraShake {
+    pub fn new(max_translation: Vec3, max_rotation: Vec3) -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.0,
+            max_translation,
+            max_rotation,
+            frequency: 15.0,
+            tick_mode: TickMode::PoleMatching,
+
+            time: 0.0,
+            translation: DynamicsState::new(Vec3::ZERO, DynamicsParams::new(10.0, 0.5, 2.0)),
+            rotation: DynamicsState::new(Vec3::ZERO, DynamicsParams::new(10.0, 0.5, 2.0)),
+            applied: None,
+        }
+    }
+
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_params(mut self, params: DynamicsParams) -> Self {
+        self.translation.params = params;
+        self.rotation.params = params;
+        self
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn offset(&self) -> (Vec3, Quat) {
+        (
+            self.translation.value(),
+            Quat::from_scaled_axis(self.rotation.value()),
+        )
+    }
+
+    fn tick(&mut self, dt: f32) {
+        self.trauma = (self.trauma - self.decay * dt).max(0.0);
+        //wrapped so the noise input keeps its precision in long sessions
+        self.time = (self.time + dt * self.frequency).rem_euclid(NOISE_PERIOD as f32);
+
+        let shake = self.trauma.squared();
+        self.translation.target = noise3(self.time, 0) * self.max_translation * shake;
+        self.rotation.target = noise3(self.time, 3) * self.max_rotation * shake;
+
+        self.translation.tick(dt, None, self.tick_mode);
+        self.rotation.tick(dt, None, self.tick_mode);
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self::new(Vec3::new(10.0, 10.0, 0.0), Vec3::new(0.0, 0.0, 0.05))
+    }
+}
+
+pub(crate) struct CameraShakePlugin;
+
+impl Plugin for CameraShakePlugin {
+    fn build(&self, app: &mut App) {
+        let schedule = DynamicsSchedule::of(app);
+        app.register_type::<CameraShake>()
+            .add_systems(First, remove_camera_shake)
+            .add_systems(schedule, tick_camera_shake.in_set(DynamicsSet::Tick))
+            .add_systems(
+                PostUpdate,
+                apply_camera_shake.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+fn tick_camera_shake(
+    mut shakes: Query<&mut CameraShake>,
+    time: Res<Time>,
+    batch_size: Res<ParallelBatchSize>,
+) {
+    let dt = time.delta_secs();
+    shakes
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|mut shake| shake.tick(dt));
+}
+
+fn apply_camera_shake(mut shakes: Query<(&mut CameraShake, &mut Transform)>) {
+    for (mut shake, mut transform) in &mut shakes {
+        let (translation, rotation) = shake.offset();
+        transform.translation += translation;
+        transform.rotation *= rotation;
+        shake.applied = Some((translation, rotation));
+    }
+}
+
+fn remove_camera_shake(mut shakes: Query<(&mut CameraShake, &mut Transform)>) {
+    for (mut shake, mut transform) in &mut shakes {
+        let Some((translation, rotation)) = shake.applied.take() else {
+            continue;
+        };
+        transform.translation -= translation;
+        transform.rotation *= rotation.inverse();
+    }
+}
+
+fn noise3(t: f32, channel: u32) -> Vec3 {
+    Vec3::new(
+        noise(t, channel),
+        noise(t, channel + 1),
+        noise(t, channel + 2),
+    )
+}
+
+// number of gradients before the noise repeats
+const NOISE_PERIOD: i32 = 4096;
+
+// 1D gradient noise in [-1, 1], with a separate random gradient per channel
+fn noise(t: f32, channel: u32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let g0 = gradient((i as i32).rem_euclid(NOISE_PERIOD), channel);
+    let g1 = gradient((i as i32 + 1).rem_euclid(NOISE_PERIOD), channel);
+    let u = f * f * (3.0 - 2.0 * f);
+    (g0 * f).lerp(g1 * (f - 1.0), u) * 2.0
+}
+
+fn gradient(i: i32, channel: u32) -> f32 {
+    let mut h = (i as u32).wrapping_mul(0x9E37_79B9) ^ channel.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7FEB_352D);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846C_A68B);
+    h ^= h >> 16;
+    (h as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, system::Resource, world::World};
+use bevy_reflect::GetTypeRegistration;
+
+/// A copy of the state of every handle, for rolling the dynamics back to an earlier frame.
+///
+/// Rollback crates that save components by [`Clone`] or through reflection can register
+/// [`DynamicsState`](crate::state::DynamicsState), [`SolverState`](crate::solver::SolverState)
+/// and [`TransformDynamicsState`](crate::transform::TransformDynamicsState) themselves instead.
+#[derive(Clone, Default)]
+pub struct DynamicsSnapshot {
+    components: Vec<Arc<dyn ComponentSnapshot>>,
+}
+
+impl DynamicsSnapshot {
+    /// Copies the state of every handle, for all types initialized with
+    /// [`init_animatable_type`](crate::DynamicsAppExt::init_animatable_type) and
+    /// [`init_solver`](crate::DynamicsAppExt::init_solver).
+    pub fn take(world: &mut World) -> Self {
+        let Some(snapshots) = world.get_resource::<RollbackComponents>() else {
+            return Self::default();
+        };
+        let snapshots = snapshots.0.clone();
+        Self {
+            components: snapshots.iter().map(|take| take(world)).collect(),
+        }
+    }
+
+    /// Writes the saved state back into the handles it was taken from. Handles spawned since
+    /// are left as they are, and handles despawned since are skipped.
+    pub fn restore(&self, world: &mut World) {
+        for component in &self.components {
+            component.restore(world);
+        }
+    }
+}
+
+trait ComponentSnapshot: Send + Sync + 'static {
+    fn restore(&self, world: &mut World);
+}
+
+impl<C: Component + Clone> ComponentSnapshot for Vec<(Entity, C)> {
+    fn restore(&self, world: &mut World) {
+        for (entity, component) in self {
+            if let Some(mut current) = world.get_mut::<C>(*entity) {
+                *current = component.clone();
+            }
+        }
+    }
+}
+
+type TakeSnapshot = fn(&mut World) -> Arc<dyn ComponentSnapshot>;
+
+#[derive(Resource, Default)]
+struct RollbackComponents(Vec<TakeSnapshot>);
+
+fn take_snapshot<C: Component + Clone>(world: &mut World) -> Arc<dyn ComponentSnapshot> {
+    let mut query = world.query::<(Entity, &C)>();
+    let components: Vec<(Entity, C)> = query
+        .iter(world)
+        .map(|(entity, component)| (entity, component.clone()))
+        .collect();
+    Arc::new(components)
+}
+
+// registers a component holding handle state for reflection and `DynamicsSnapshot`
+pub(crate) fn register_rollback_component<C: Component + Clone + GetTypeRegistration>(
+    app: &mut App,
+) {
+    app.register_type::<C>()
+        .world_mut()
+        .get_resource_or_insert_with(RollbackComponents::default)
+        .0
+        .push(take_snapshot::<C>);
+}
@@ -0,0 +1,113 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_reflect::{FromReflect, Reflect, Reflectable, TypePath};
+use bevy_time::Time;
+
+use crate::{
+    snapshot::register_rollback_component,
+    state::{DynamicsSet, DynamicsState},
+    AnimValue, DynamicsSchedule, ParallelBatchSize,
+};
+
+/// A model driving the value of a handle towards its target, as an alternative to the
+/// second-order dynamics of [`DynamicsParams`](crate::state::DynamicsParams).
+///
+/// Solvers, including the ones in [`filter`](crate::filter) and [`pid`](crate::pid), are
+/// registered per value type with [`init_solver`](crate::DynamicsAppExt::init_solver) and
+/// ticked in [`DynamicsSet::Tick`]. Only registered solvers cost a system, and handles of a
+/// solver that isn't registered for their type are never ticked. Handles driven by a solver are
+/// otherwise ordinary handles: they read their sources, can be bound with
+/// [`Dynamics`](crate::component::Dynamics), and respect their constraints.
+pub trait Solver<T: AnimValue>: TypePath + Send + Sync + 'static {
+    type Params: Clone + FromReflect + Reflectable + Send + Sync + 'static;
+    /// Anything the solver keeps between ticks besides the value and velocity of the
+    /// handle, such as an acceleration or an integral.
+    type State: Default + Clone + FromReflect + Reflectable + Send + Sync + 'static;
+
+    /// Advances `value` and `velocity` by `dt`, given the current target and its velocity.
+    fn tick(
+        params: &Self::Params,
+        state: &mut Self::State,
+        value: &mut T,
+        velocity: &mut T,
+        dt: f32,
+        target: T,
+        d_target: T,
+    );
+}
+
+/// The params and state of a handle driven by the solver `S`, next to its
+/// [`DynamicsState`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct SolverState<T: AnimValue, S: Solver<T>> {
+    pub(crate) params: S::Params,
+    pub(crate) state: S::State,
+    #[reflect(ignore)]
+    _data: PhantomData<fn(T)>,
+}
+
+impl<T: AnimValue, S: Solver<T>> Clone for SolverState<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            params: self.params.clone(),
+            state: self.state.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: AnimValue, S: Solver<T>> SolverState<T, S> {
+    pub(crate) fn new(params: S::Params) -> Self {
+        Self {
+            params,
+            state: Default::default(),
+            _data: PhantomData,
+        }
+    }
+
+    pub(crate) fn set_params(&mut self, params: S::Params) {
+        self.params = params;
+        self.state = Default::default();
+    }
+}
+
+pub(crate) struct SolverPlugin<T: AnimValue, S: Solver<T>>(PhantomData<fn(T, S)>);
+
+impl<T: AnimValue, S: Solver<T>> Default for SolverPlugin<T, S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: AnimValue, S: Solver<T>> Plugin for SolverPlugin<T, S> {
+    fn build(&self, app: &mut App) {
+        register_rollback_component::<SolverState<T, S>>(app);
+        app.add_systems(
+            DynamicsSchedule::of(app),
+            tick_solver::<T, S>.in_set(DynamicsSet::Tick),
+        );
+    }
+}
+
+fn tick_solver<T: AnimValue, S: Solver<T>>(
+    mut dynamics: Query<(&mut DynamicsState<T>, &mut SolverState<T, S>)>,
+    time: Res<Time>,
+    batch_size: Res<ParallelBatchSize>,
+) {
+    let dt = time.delta_secs();
+    dynamics
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|(mut d, mut solver)| {
+            let SolverState { params, state, .. } = solver.as_mut();
+            d.tick_solver::<S>(dt, params, state);
+        });
+}
@@ -0,0 +1,27 @@
+use bevy_ecs::{
+    entity::Entity,
+    system::{IntoSystem, Query, System},
+};
+use bevy_math::Vec3;
+use bevy_transform::components::GlobalTransform;
+
+/// The world-space translation of `entity`, as of the last transform propagation (see
+/// [`WorldSpaceProperty`](crate::props::WorldSpaceProperty) for the frame of lag this implies).
+pub fn global_translation(entity: Entity) -> impl System<In = (), Out = Vec3> {
+    IntoSystem::into_system(move |transforms: Query<&GlobalTransform>| {
+        transforms
+            .get(entity)
+            .map(GlobalTransform::translation)
+            .unwrap_or(Vec3::ZERO)
+    })
+}
+
+/// The world-space scale of `entity`, as of the last transform propagation.
+pub fn global_scale(entity: Entity) -> impl System<In = (), Out = Vec3> {
+    IntoSystem::into_system(move |transforms: Query<&GlobalTransform>| {
+        transforms
+            .get(entity)
+            .map(GlobalTransform::scale)
+            .unwrap_or(Vec3::ONE)
+    })
+}
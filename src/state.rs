@@ -1,13 +1,16 @@
-use crate::AnimValue;
+use crate::{
+    constraint::DynamicsConstraints, path::ReflectAnimValue, snapshot::register_rollback_component,
+    solver::Solver, AnimValue, DynamicsSchedule, ParallelBatchSize,
+};
 
-use std::{f32::consts::PI, fmt::Debug, marker::PhantomData};
+use std::{any::type_name, f32::consts::PI, fmt::Debug, marker::PhantomData};
 
-use bevy_app::App;
-use bevy_app::{Plugin, PreUpdate};
-use bevy_ecs::system::SystemId;
+use bevy_app::{App, Plugin};
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::system::{EntityCommands, SystemId};
 use bevy_ecs::{
     component::Component,
-    entity::Entity,
+    entity::{Entity, EntityMapper, MapEntities},
     prelude::Local,
     query::{QueryState, With},
     schedule::{IntoSystemConfigs, SystemSet},
@@ -15,46 +18,78 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_math::ops::{self, FloatPow};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_time::Time;
+use bevy_utils::{tracing::warn, warn_once};
 
-pub struct AnimHandle<T: AnimValue> {
+/// A handle to an animated value, which is the entity holding its state.
+#[derive(Reflect)]
+pub struct AnimHandle<T: Send + Sync + 'static> {
     pub(crate) entity: Entity,
+    #[reflect(ignore)]
     _data: PhantomData<T>,
 }
 
-impl<T: AnimValue> Copy for AnimHandle<T> {}
+impl<T: Send + Sync + 'static> Copy for AnimHandle<T> {}
 
-impl<T: AnimValue> Clone for AnimHandle<T> {
+impl<T: Send + Sync + 'static> Clone for AnimHandle<T> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<T: AnimValue> PartialEq for AnimHandle<T> {
+impl<T: Send + Sync + 'static> PartialEq for AnimHandle<T> {
     fn eq(&self, other: &Self) -> bool {
         self.entity == other.entity
     }
 }
 
-impl<T: AnimValue> Eq for AnimHandle<T> {}
+impl<T: Send + Sync + 'static> Eq for AnimHandle<T> {}
 
-impl<T: AnimValue> AnimHandle<T> {
+impl<T: Send + Sync + 'static> AnimHandle<T> {
     pub(crate) fn new(entity: Entity) -> Self {
         Self {
             entity,
             _data: PhantomData,
         }
     }
+
+    /// The handle of a value of type `T` held by `entity`, e.g. a handle loaded from a scene.
+    pub fn from_entity(entity: Entity) -> Self {
+        Self::new(entity)
+    }
+
+    pub fn entity(self) -> Entity {
+        self.entity
+    }
 }
 
-#[derive(Component)]
-pub(crate) struct DynamicsState<T: AnimValue> {
+impl<T: Send + Sync + 'static> MapEntities for AnimHandle<T> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
+
+/// The state of a handle driven by second-order dynamics or a [`Solver`]. Cloneable and
+/// reflected, so rollback crates can save and restore it along with the rest of the world.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct DynamicsState<T: AnimValue> {
     pub target: T,
     pub params: DynamicsParams,
+    pub constraints: DynamicsConstraints<T>,
+    // overrides `params` with one set per lane
+    axis_params: Option<Vec<DynamicsParams>>,
 
     prev_target: T,
     current: T,
     d_current: T,
+    // external acceleration accumulated since the last tick
+    force: T,
+    // set when the target jumps, so the next tick doesn't see the jump as target velocity
+    skip_target_velocity: bool,
+    // set when the handle jumps, so its solver state doesn't pull it back
+    reset_solver: bool,
 }
 
 impl<T: AnimValue> DynamicsState<T> {
@@ -63,15 +98,55 @@ impl<T: AnimValue> DynamicsState<T> {
             target,
             prev_target: target,
             params,
+            constraints: Default::default(),
+            axis_params: None,
 
             current: target,
             d_current: T::ZERO,
+            force: T::ZERO,
+            skip_target_velocity: false,
+            reset_solver: false,
         }
     }
 
     pub fn value(&self) -> T {
         self.current
     }
+
+    pub fn velocity(&self) -> T {
+        self.d_current
+    }
+
+    pub fn snap_to(&mut self, value: T) {
+        self.set_state(value, T::ZERO);
+        self.teleport_target(value);
+    }
+
+    pub fn reset_velocity(&mut self) {
+        self.d_current = T::ZERO;
+        self.prev_target = self.target;
+        self.skip_target_velocity = true;
+    }
+
+    pub fn set_state(&mut self, value: T, velocity: T) {
+        self.current = value;
+        self.d_current = velocity;
+    }
+
+    pub fn teleport_target(&mut self, target: T) {
+        self.target = target;
+        self.prev_target = target;
+        self.skip_target_velocity = true;
+        self.reset_solver = true;
+    }
+
+    pub fn apply_impulse(&mut self, impulse: T) {
+        self.d_current = self.d_current + impulse;
+    }
+
+    pub fn add_force(&mut self, force: T) {
+        self.force = self.force + force;
+    }
 }
 
 impl<T: AnimValue> Default for DynamicsState<T> {
@@ -80,66 +155,211 @@ impl<T: AnimValue> Default for DynamicsState<T> {
     }
 }
 
+impl<T: AnimValue> SourceTarget for DynamicsState<T> {
+    type Value = T;
+
+    fn set_target(&mut self, target: T) {
+        self.target = target;
+    }
+}
+
 impl<T: AnimValue> DynamicsState<T> {
-    fn tick(&mut self, dt: f32, d_target: Option<T>, k1: f32, k2: f32, k3: f32) {
-        let d_target = d_target.unwrap_or_else(|| (self.target - self.prev_target) / dt);
+    /// Gives each lane its own params, overriding `params`. A `Vec` without exactly one
+    /// params per [`AnimValue::LANES`] is ignored with a warning.
+    pub fn set_axis_params(&mut self, axis_params: Option<Vec<DynamicsParams>>) {
+        if let Some(len) = axis_params
+            .as_ref()
+            .map(Vec::len)
+            .filter(|&len| len != T::LANES)
+        {
+            warn!(
+                "ignoring {len} axis params for a `{}` with {} lanes",
+                type_name::<T>(),
+                T::LANES
+            );
+            return;
+        }
+        self.axis_params = axis_params;
+    }
+
+    /// Advances the handle by `dt` towards its target, as the tick systems do. `d_target`
+    /// overrides the target velocity, which is otherwise estimated from the last tick.
+    pub fn tick(&mut self, dt: f32, d_target: Option<T>, mode: TickMode) {
+        //axis params restored from a scene or snapshot skip the check of `set_axis_params`
+        let axis_params = match &self.axis_params {
+            Some(axis_params) if axis_params.len() != T::LANES => {
+                warn_once!(
+                    "{} axis params for a `{}` with {} lanes, using its params instead",
+                    axis_params.len(),
+                    type_name::<T>(),
+                    T::LANES
+                );
+                None
+            }
+            axis_params => axis_params.as_ref(),
+        };
+        let Some(axis_params) = axis_params else {
+            let DynamicsParams { k1, k2, k3, .. } = self.params;
+            if mode == TickMode::Analytic {
+                let response = self.params.homogeneous_response(dt);
+                self.tick_analytic(dt, d_target, k3 - k1, k2, response);
+            } else {
+                let (k1, k2, k3) = self.params.coefficients(mode, dt);
+                self.integrate(dt, d_target, k1, k2, k3);
+            }
+            return;
+        };
+
+        //same as above, with each coefficient gathered into a value holding one per lane
+        let (mut k1, mut k2, mut k3) = (T::ZERO, T::ZERO, T::ZERO);
+        if mode == TickMode::Analytic {
+            let mut response = [T::ZERO; 4];
+            for (i, params) in axis_params.iter().enumerate() {
+                *k1.lane_mut(i) = params.k1;
+                *k2.lane_mut(i) = params.k2;
+                *k3.lane_mut(i) = params.k3;
+                for (coefficient, lane) in response.iter_mut().zip(params.homogeneous_response(dt))
+                {
+                    *coefficient.lane_mut(i) = lane;
+                }
+            }
+            self.tick_analytic(
+                dt,
+                d_target,
+                PerLane(k3 - k1),
+                PerLane(k2),
+                response.map(PerLane),
+            );
+        } else {
+            for (i, params) in axis_params.iter().enumerate() {
+                (*k1.lane_mut(i), *k2.lane_mut(i), *k3.lane_mut(i)) = params.coefficients(mode, dt);
+            }
+            self.integrate(dt, d_target, PerLane(k1), PerLane(k2), PerLane(k3));
+        }
+    }
+
+    fn target_velocity(&mut self, dt: f32, d_target: Option<T>) -> T {
+        let skip_target_velocity = std::mem::take(&mut self.skip_target_velocity);
+        let d_target = match d_target {
+            Some(d_target) => d_target,
+            None if skip_target_velocity => T::ZERO,
+            None => self.target.difference(self.prev_target) / dt,
+        };
         self.prev_target = self.target;
+        d_target
+    }
+
+    fn integrate<C: Coefficient<T>>(&mut self, dt: f32, d_target: Option<T>, k1: C, k2: C, k3: C) {
+        let d_target = self.target_velocity(dt, d_target);
 
         self.current = self.current + self.d_current * dt;
-        self.d_current = self.d_current
-            + (self.target + d_target * k3 - self.current - self.d_current * k1) / k2 * dt;
+        self.constraints
+            .apply_bounds(&mut self.current, &mut self.d_current);
+
+        let error = self.target.difference(self.current);
+        let force = std::mem::replace(&mut self.force, T::ZERO);
+        let d2_current = self
+            .constraints
+            .limit_acceleration(k2.div(error + k3.mul(d_target) - k1.mul(self.d_current)) + force);
+        self.d_current = self
+            .constraints
+            .limit_speed(self.d_current + d2_current * dt);
     }
 
-    fn tick_simple(&mut self, dt: f32, d_target: Option<T>) {
-        let DynamicsParams { k1, k2, k3, .. } = self.params;
-        self.tick(dt, d_target, k1, k2, k3);
+    pub(crate) fn tick_solver<S: Solver<T>>(
+        &mut self,
+        dt: f32,
+        params: &S::Params,
+        state: &mut S::State,
+    ) {
+        if std::mem::take(&mut self.reset_solver) {
+            *state = Default::default();
+        }
+        let d_target = self.target_velocity(dt, None);
+        let force = std::mem::replace(&mut self.force, T::ZERO);
+        self.d_current = self.d_current + force * dt;
+
+        S::tick(
+            params,
+            state,
+            &mut self.current,
+            &mut self.d_current,
+            dt,
+            self.target,
+            d_target,
+        );
+
+        self.constraints
+            .apply_bounds(&mut self.current, &mut self.d_current);
+        self.d_current = self.constraints.limit_speed(self.d_current);
     }
 
-    fn tick_stable(&mut self, dt: f32, d_target: Option<T>) {
-        let DynamicsParams { k1, k2, k3, .. } = self.params;
-        let k2_stable = k2.max(k1 * dt).max((dt.squared() + k1 * dt) / 2.0);
-        self.tick(dt, d_target, k1, k2_stable, k3);
+    // Solves the system exactly for a target moving linearly over the step, ending at
+    // `target`. The particular solution follows the target offset by `(k3 - k1) * d_target`
+    // (and `k2 * force`), and the error from it decays by the homogeneous response.
+    fn tick_analytic<C: Coefficient<T>>(
+        &mut self,
+        dt: f32,
+        d_target: Option<T>,
+        k3_minus_k1: C,
+        k2: C,
+        [a, b, c, d]: [C; 4],
+    ) {
+        let d_target = self.target_velocity(dt, d_target);
+        let force = std::mem::replace(&mut self.force, T::ZERO);
+
+        let offset = k3_minus_k1.mul(d_target) + k2.mul(force);
+        let start = self.target - d_target * dt;
+        let error = self.current.difference(start) - offset;
+        let d_error = self.d_current - d_target;
+
+        self.current = self.target + offset + a.mul(error) + b.mul(d_error);
+        self.d_current = d_target + c.mul(error) + d.mul(d_error);
+
+        self.constraints
+            .apply_bounds(&mut self.current, &mut self.d_current);
+        self.d_current = self.constraints.limit_speed(self.d_current);
     }
+}
 
-    fn tick_pole_matching(&mut self, dt: f32, d_target: Option<T>) {
-        let DynamicsParams {
-            k1,
-            k2,
-            k3,
-            w,
-            z,
-            d,
-        } = self.params;
+// A coefficient of the dynamics, either shared by all lanes or given per lane.
+trait Coefficient<T>: Copy {
+    fn mul(self, value: T) -> T;
+    fn div(self, value: T) -> T;
+}
 
-        let (k1_stable, k2_stable) = if w * dt < z {
-            (k1, k2.max(dt * k1).max((dt.squared() + k1 * dt) / 2.0))
-        } else {
-            //use pole matching when the system is very fast
-            let t1 = ops::exp(-z * w * dt);
-            let alpha = 2.0
-                * t1
-                * if z <= 1.0 {
-                    ops::cos(d * dt)
-                } else {
-                    ops::cosh(d * dt)
-                };
-            let beta = t1.squared();
-            let t2 = (beta - alpha + 1.0).recip() * dt;
-            (t2 * (1.0 - beta), t2 * dt)
-        };
+impl<T: AnimValue> Coefficient<T> for f32 {
+    fn mul(self, value: T) -> T {
+        value * self
+    }
+
+    fn div(self, value: T) -> T {
+        value / self
+    }
+}
+
+#[derive(Copy, Clone)]
+struct PerLane<T>(T);
+
+impl<T: AnimValue> Coefficient<T> for PerLane<T> {
+    fn mul(self, value: T) -> T {
+        value.mul_lanes(self.0)
+    }
 
-        self.tick(dt, d_target, k1_stable, k2_stable, k3);
+    fn div(self, value: T) -> T {
+        value.div_lanes(self.0)
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Reflect)]
 pub struct DynamicsParams {
-    k1: f32,
-    k2: f32,
-    k3: f32,
-    w: f32,
-    z: f32,
-    d: f32,
+    pub(crate) k1: f32,
+    pub(crate) k2: f32,
+    pub(crate) k3: f32,
+    pub(crate) w: f32,
+    pub(crate) z: f32,
+    pub(crate) d: f32,
+    r: f32,
 }
 
 impl Default for DynamicsParams {
@@ -165,20 +385,158 @@ impl DynamicsParams {
             w,
             z,
             d,
+            r: response,
         }
     }
 
     pub fn smooth_damp(frequency: f32) -> Self {
         Self::new(frequency, 1.0, 0.0)
     }
+
+    /// Critically damped params that settle within 2% of a step in `settling_time` seconds.
+    pub fn from_settling_time(settling_time: f32) -> Self {
+        Self::from_angular_frequency(CRITICAL_SETTLING / settling_time, 1.0)
+    }
+
+    /// Params that overshoot a step by `overshoot` (as a fraction, e.g. `0.1` for 10%) and
+    /// settle within roughly 2% of it in `settling_time` seconds.
+    pub fn from_settling_time_and_overshoot(settling_time: f32, overshoot: f32) -> Self {
+        if overshoot <= 0.0 {
+            return Self::from_settling_time(settling_time);
+        }
+        let z = overshoot_damping(overshoot);
+        Self::from_angular_frequency(4.0 / (z * settling_time), z)
+    }
+
+    /// Params that overshoot a step by `overshoot` (as a fraction, e.g. `0.1` for 10%) and
+    /// oscillate around it with the given `period` in seconds.
+    pub fn from_overshoot_and_period(overshoot: f32, period: f32) -> Self {
+        let z = overshoot_damping(overshoot);
+        Self::from_angular_frequency(2.0 * PI / (period * (1.0 - z.squared()).sqrt()), z)
+    }
+
+    /// Critically damped params that get halfway to a step in `half_life` seconds.
+    pub fn from_half_life(half_life: f32) -> Self {
+        Self::from_angular_frequency(CRITICAL_HALF_LIFE / half_life, 1.0)
+    }
+
+    /// Params of a physical damped spring pulling a `mass` towards the target.
+    pub fn from_spring(stiffness: f32, mass: f32, damping: f32) -> Self {
+        let w = (stiffness / mass).sqrt();
+        Self::from_angular_frequency(w, damping / (2.0 * (stiffness * mass).sqrt()))
+    }
+
+    pub fn with_response(self, response: f32) -> Self {
+        Self::new(self.frequency(), self.damping(), response)
+    }
+
+    /// The natural frequency, in Hz.
+    pub fn frequency(&self) -> f32 {
+        self.w / (2.0 * PI)
+    }
+
+    pub fn damping(&self) -> f32 {
+        self.z
+    }
+
+    pub fn response(&self) -> f32 {
+        self.r
+    }
+
+    fn from_angular_frequency(w: f32, damping: f32) -> Self {
+        Self::new(w / (2.0 * PI), damping, 0.0)
+    }
+
+    pub(crate) fn coefficients(&self, mode: TickMode, dt: f32) -> (f32, f32, f32) {
+        let DynamicsParams {
+            k1,
+            k2,
+            k3,
+            w,
+            z,
+            d,
+            ..
+        } = *self;
+
+        match mode {
+            TickMode::Simple => (k1, k2, k3),
+            TickMode::Stable => (k1, k2.max(k1 * dt).max((dt.squared() + k1 * dt) / 2.0), k3),
+            TickMode::PoleMatching | TickMode::Analytic if w * dt < z => {
+                (k1, k2.max(dt * k1).max((dt.squared() + k1 * dt) / 2.0), k3)
+            }
+            //analytic ticks only use these for values without a closed form, like rotations
+            TickMode::PoleMatching | TickMode::Analytic => {
+                //use pole matching when the system is very fast
+                let t1 = ops::exp(-z * w * dt);
+                let alpha = 2.0
+                    * t1
+                    * if z <= 1.0 {
+                        ops::cos(d * dt)
+                    } else {
+                        ops::cosh(d * dt)
+                    };
+                let beta = t1.squared();
+                let t2 = (beta - alpha + 1.0).recip() * dt;
+                (t2 * (1.0 - beta), t2 * dt, k3)
+            }
+        }
+    }
+
+    // Maps the error and its derivative at the start of a step of length `t` to their values at
+    // the end, for the unforced system `e'' + 2zw e' + w^2 e = 0`: returns `[a, b, c, d]` with
+    // `e(t) = a e + b e'` and `e'(t) = c e + d e'`.
+    pub(crate) fn homogeneous_response(&self, t: f32) -> [f32; 4] {
+        let DynamicsParams { w, z, d, .. } = *self;
+        let zw = z * w;
+
+        //`exp(-zwt) cos(dt)` and `exp(-zwt) sin(dt) / d`
+        let (cos, sin_over_d) = if d * t < 1e-4 {
+            //keep the decay but take the oscillation to first order in `dt`, which is also the
+            //limit of both branches below as d goes to 0
+            let e = ops::exp(-zw * t);
+            (e, e * t)
+        } else if z < 1.0 {
+            let e = ops::exp(-zw * t);
+            let (sin, cos) = ops::sin_cos(d * t);
+            (e * cos, e * sin / d)
+        } else {
+            //exp(-zwt) cosh(dt) and exp(-zwt) sinh(dt), without overflowing for large t
+            let p = ops::exp((d - zw) * t);
+            let q = ops::exp(-(d + zw) * t);
+            ((p + q) / 2.0, (p - q) / (2.0 * d))
+        };
+
+        [
+            cos + zw * sin_over_d,
+            sin_over_d,
+            -w.squared() * sin_over_d,
+            cos - zw * sin_over_d,
+        ]
+    }
 }
 
-#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+// `w * t` at which a critically damped step response is within 2% of, and halfway to, the target
+const CRITICAL_SETTLING: f32 = 5.833_922;
+const CRITICAL_HALF_LIFE: f32 = 1.678_347;
+
+// damping ratio of a step response overshooting by the given fraction
+fn overshoot_damping(overshoot: f32) -> f32 {
+    let log = ops::ln(overshoot.clamp(f32::EPSILON, 1.0));
+    -log / (PI.squared() + log.squared()).sqrt()
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
 pub enum TickMode {
+    /// Semi-implicit Euler with the params as given. Diverges once `dt` gets large compared
+    /// to the period of fast params, which the other modes guard against.
     Simple,
     #[default]
     Stable,
     PoleMatching,
+    /// Closed-form solution, exact for targets moving linearly within a frame. A target that
+    /// jumps is treated as moving linearly over the frame of the jump, so step responses
+    /// still depend slightly on `dt`. Acceleration limits aren't applied in this mode.
+    Analytic,
 }
 
 pub struct AnimValuePlugin<T: AnimValue>(PhantomData<fn(T)>);
@@ -191,28 +549,74 @@ impl<T: AnimValue> Default for AnimValuePlugin<T> {
 
 impl<T: AnimValue> Plugin for AnimValuePlugin<T> {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreUpdate, update_sources::<T>.in_set(DynamicsSet::Read))
-            .add_systems(
-                PreUpdate,
-                (
-                    tick_dynamics_simple::<T>,
-                    tick_dynamics_stable::<T>,
-                    tick_dynamics_pole_matching::<T>,
-                )
-                    .in_set(DynamicsSet::Tick),
-            );
+        let schedule = DynamicsSchedule::of(app);
+        app.add_systems(
+            schedule,
+            update_sources::<DynamicsState<T>>.in_set(DynamicsSet::Read),
+        )
+        .add_systems(
+            schedule,
+            (
+                tick_dynamics::<T, TickSimple>,
+                tick_dynamics::<T, TickStable>,
+                tick_dynamics::<T, TickPoleMatching>,
+                tick_dynamics::<T, TickAnalytic>,
+            )
+                .in_set(DynamicsSet::Tick),
+        );
+
+        register_rollback_component::<DynamicsState<T>>(app);
+        app.register_type::<T>()
+            .register_type_data::<T, ReflectAnimValue>();
     }
 }
 
-#[derive(Component)]
+pub(crate) trait TickModeMarker: Component {
+    const MODE: TickMode;
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub(crate) struct TickSimple;
 
-#[derive(Component)]
+impl TickModeMarker for TickSimple {
+    const MODE: TickMode = TickMode::Simple;
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub(crate) struct TickStable;
 
-#[derive(Component)]
+impl TickModeMarker for TickStable {
+    const MODE: TickMode = TickMode::Stable;
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub(crate) struct TickPoleMatching;
 
+impl TickModeMarker for TickPoleMatching {
+    const MODE: TickMode = TickMode::PoleMatching;
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct TickAnalytic;
+
+impl TickModeMarker for TickAnalytic {
+    const MODE: TickMode = TickMode::Analytic;
+}
+
+pub(crate) fn set_tick_mode(entity: &mut EntityCommands, mode: TickMode) {
+    entity.remove::<(TickSimple, TickStable, TickPoleMatching, TickAnalytic)>();
+    match mode {
+        TickMode::Simple => entity.insert(TickSimple),
+        TickMode::Stable => entity.insert(TickStable),
+        TickMode::PoleMatching => entity.insert(TickPoleMatching),
+        TickMode::Analytic => entity.insert(TickAnalytic),
+    };
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, SystemSet)]
 pub enum DynamicsSet {
     All,
@@ -225,54 +629,42 @@ pub(super) fn non_zero_delta(time: Res<Time>) -> bool {
     time.delta_secs() > 0.0
 }
 
-fn tick_dynamics_simple<T: AnimValue>(
-    mut dynamics: Query<&mut DynamicsState<T>, With<TickSimple>>,
+fn tick_dynamics<T: AnimValue, M: TickModeMarker>(
+    mut dynamics: Query<&mut DynamicsState<T>, With<M>>,
     time: Res<Time>,
+    batch_size: Res<ParallelBatchSize>,
 ) {
     let dt = time.delta_secs();
     dynamics
-        .iter_mut()
-        .for_each(|mut d| d.tick_simple(dt, None));
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|mut d| d.tick(dt, None, M::MODE));
 }
 
-fn tick_dynamics_stable<T: AnimValue>(
-    mut dynamics: Query<&mut DynamicsState<T>, With<TickStable>>,
-    time: Res<Time>,
-) {
-    let dt = time.delta_secs();
-    dynamics
-        .iter_mut()
-        .for_each(|mut d| d.tick_stable(dt, None));
-}
+#[derive(Component)]
+pub(crate) struct DynamicsSource<T: Send + Sync + 'static>(pub SystemId<(), T>);
 
-fn tick_dynamics_pole_matching<T: AnimValue>(
-    mut dynamics: Query<&mut DynamicsState<T>, With<TickPoleMatching>>,
-    time: Res<Time>,
-) {
-    let dt = time.delta_secs();
-    dynamics
-        .iter_mut()
-        .for_each(|mut d| d.tick_pole_matching(dt, None));
-}
+pub(crate) trait SourceTarget: Component {
+    type Value: Send + Sync + 'static;
 
-#[derive(Component)]
-pub(crate) struct DynamicsSource<T: AnimValue>(pub SystemId<(), T>);
+    fn set_target(&mut self, target: Self::Value);
+}
 
 //TODO: GROSS
 #[allow(clippy::type_complexity)]
-fn update_sources<T: AnimValue>(
+pub(crate) fn update_sources<S: SourceTarget>(
     world: &mut World,
-    query: &mut QueryState<Entity, (With<DynamicsState<T>>, With<DynamicsSource<T>>)>,
+    query: &mut QueryState<Entity, (With<S>, With<DynamicsSource<S::Value>>)>,
     mut entities: Local<Vec<Entity>>,
 ) {
     entities.extend(query.iter(world));
     for entity in entities.drain(..) {
         let mut entity = world.entity_mut(entity);
-        let callback_id = entity.get::<DynamicsSource<T>>().unwrap().0;
+        let callback_id = entity.get::<DynamicsSource<S::Value>>().unwrap().0;
         let Ok(val) = entity.world_scope(|world| world.run_system(callback_id)) else {
             //TODO: warn
             continue;
         };
-        entity.get_mut::<DynamicsState<T>>().unwrap().target = val;
+        entity.get_mut::<S>().unwrap().set_target(val);
     }
 }
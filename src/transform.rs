@@ -0,0 +1,223 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use crate::{
+    snapshot::register_rollback_component,
+    state::{
+        update_sources, DynamicsParams, DynamicsSet, DynamicsState, SourceTarget, TickAnalytic,
+        TickMode, TickModeMarker, TickPoleMatching, TickSimple, TickStable,
+    },
+    DynamicsSchedule, ParallelBatchSize,
+};
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TransformParams {
+    pub translation: DynamicsParams,
+    pub rotation: DynamicsParams,
+    pub scale: DynamicsParams,
+}
+
+impl TransformParams {
+    pub fn new(
+        translation: DynamicsParams,
+        rotation: DynamicsParams,
+        scale: DynamicsParams,
+    ) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn uniform(params: DynamicsParams) -> Self {
+        Self::new(params, params, params)
+    }
+}
+
+impl From<DynamicsParams> for TransformParams {
+    fn from(params: DynamicsParams) -> Self {
+        Self::uniform(params)
+    }
+}
+
+/// The state of a handle animating a whole [`Transform`].
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct TransformDynamicsState {
+    translation: DynamicsState<Vec3>,
+    rotation: RotationDynamics,
+    scale: DynamicsState<Vec3>,
+}
+
+impl TransformDynamicsState {
+    pub(crate) fn new(target: Transform, params: TransformParams) -> Self {
+        Self {
+            translation: DynamicsState::new(target.translation, params.translation),
+            rotation: RotationDynamics::new(target.rotation, params.rotation),
+            scale: DynamicsState::new(target.scale, params.scale),
+        }
+    }
+
+    pub fn value(&self) -> Transform {
+        Transform {
+            translation: self.translation.value(),
+            rotation: self.rotation.current,
+            scale: self.scale.value(),
+        }
+    }
+
+    /// Jumps the handle to `value` at rest, like [`DynamicsState::snap_to`].
+    pub fn snap_to(&mut self, value: Transform) {
+        self.translation.snap_to(value.translation);
+        self.rotation.snap_to(value.rotation);
+        self.scale.snap_to(value.scale);
+    }
+
+    pub fn reset_velocity(&mut self) {
+        self.translation.reset_velocity();
+        self.rotation.reset_velocity();
+        self.scale.reset_velocity();
+    }
+
+    /// Moves the target without generating target velocity, like
+    /// [`DynamicsState::teleport_target`].
+    pub fn teleport_target(&mut self, target: Transform) {
+        self.translation.teleport_target(target.translation);
+        self.rotation.teleport_target(target.rotation);
+        self.scale.teleport_target(target.scale);
+    }
+
+    fn tick(&mut self, dt: f32, mode: TickMode) {
+        self.translation.tick(dt, None, mode);
+        self.rotation.tick(dt, mode);
+        self.scale.tick(dt, None, mode);
+    }
+}
+
+impl SourceTarget for TransformDynamicsState {
+    type Value = Transform;
+
+    fn set_target(&mut self, target: Transform) {
+        self.translation.target = target.translation;
+        self.rotation.target = target.rotation;
+        self.scale.target = target.scale;
+    }
+}
+
+/// Second-order dynamics on SO(3). The error and velocities are angular velocities
+/// in world space, and the current rotation is advanced along the exponential map.
+#[derive(Clone, Reflect)]
+struct RotationDynamics {
+    target: Quat,
+    params: DynamicsParams,
+
+    prev_target: Quat,
+    current: Quat,
+    d_current: Vec3,
+    // set when the target jumps, so the next tick doesn't see the jump as target velocity
+    skip_target_velocity: bool,
+}
+
+impl RotationDynamics {
+    fn new(target: Quat, params: DynamicsParams) -> Self {
+        Self {
+            target,
+            prev_target: target,
+            params,
+
+            current: target,
+            d_current: Vec3::ZERO,
+            skip_target_velocity: false,
+        }
+    }
+
+    fn snap_to(&mut self, value: Quat) {
+        self.current = value;
+        self.d_current = Vec3::ZERO;
+        self.teleport_target(value);
+    }
+
+    fn reset_velocity(&mut self) {
+        self.d_current = Vec3::ZERO;
+        self.prev_target = self.target;
+        self.skip_target_velocity = true;
+    }
+
+    fn teleport_target(&mut self, target: Quat) {
+        self.target = target;
+        self.prev_target = target;
+        self.skip_target_velocity = true;
+    }
+
+    fn tick(&mut self, dt: f32, mode: TickMode) {
+        let (k1, k2, k3) = self.params.coefficients(mode, dt);
+
+        let d_target = if std::mem::take(&mut self.skip_target_velocity) {
+            Vec3::ZERO
+        } else {
+            rotation_delta(self.target, self.prev_target) / dt
+        };
+        self.prev_target = self.target;
+
+        self.current = (Quat::from_scaled_axis(self.d_current * dt) * self.current).normalize();
+        let error = rotation_delta(self.target, self.current);
+        self.d_current = self.d_current + (error + d_target * k3 - self.d_current * k1) / k2 * dt;
+    }
+}
+
+// shortest rotation taking `from` to `to`, as a scaled axis
+fn rotation_delta(to: Quat, from: Quat) -> Vec3 {
+    let delta = to * from.inverse();
+    if delta.w < 0.0 {
+        (-delta).to_scaled_axis()
+    } else {
+        delta.to_scaled_axis()
+    }
+}
+
+pub(crate) struct TransformDynamicsPlugin;
+
+impl Plugin for TransformDynamicsPlugin {
+    fn build(&self, app: &mut App) {
+        register_rollback_component::<TransformDynamicsState>(app);
+
+        let schedule = DynamicsSchedule::of(app);
+        app.add_systems(
+            schedule,
+            update_sources::<TransformDynamicsState>.in_set(DynamicsSet::Read),
+        )
+        .add_systems(
+            schedule,
+            (
+                tick_transform_dynamics::<TickSimple>,
+                tick_transform_dynamics::<TickStable>,
+                tick_transform_dynamics::<TickPoleMatching>,
+                tick_transform_dynamics::<TickAnalytic>,
+            )
+                .in_set(DynamicsSet::Tick),
+        );
+    }
+}
+
+fn tick_transform_dynamics<M: TickModeMarker>(
+    mut dynamics: Query<&mut TransformDynamicsState, With<M>>,
+    time: Res<Time>,
+    batch_size: Res<ParallelBatchSize>,
+) {
+    let dt = time.delta_secs();
+    dynamics
+        .par_iter_mut()
+        .batching_strategy(batch_size.strategy())
+        .for_each(|mut d| d.tick(dt, M::MODE));
+}
@@ -0,0 +1,158 @@
+use std::f32::consts::PI;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use bevy_math::VectorSpace;
+use bevy_reflect::{Reflect, TypePath};
+
+use crate::AnimValue;
+
+/// A periodic range `[START, END)`.
+pub trait Period: TypePath + Send + Sync + 'static {
+    const START: f32;
+    const END: f32;
+}
+
+#[derive(TypePath)]
+pub struct Radians;
+
+impl Period for Radians {
+    const START: f32 = -PI;
+    const END: f32 = PI;
+}
+
+#[derive(TypePath)]
+pub struct Degrees;
+
+impl Period for Degrees {
+    const START: f32 = 0.0;
+    const END: f32 = 360.0;
+}
+
+#[derive(TypePath)]
+pub struct UnitInterval;
+
+impl Period for UnitInterval {
+    const START: f32 = 0.0;
+    const END: f32 = 1.0;
+}
+
+/// A scalar on a periodic range. Arithmetic is unwrapped, so the animated value may leave
+/// the range, but differences always take the shortest signed way around.
+#[derive(Reflect)]
+pub struct Wrapping<P: Period>(pub f32, #[reflect(ignore)] PhantomData<P>);
+
+/// An angle in radians.
+pub type Angle = Wrapping<Radians>;
+
+impl<P: Period> Wrapping<P> {
+    pub const fn new(value: f32) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// The value mapped back into `[START, END)`.
+    pub fn wrapped(self) -> f32 {
+        (self.0 - P::START).rem_euclid(P::END - P::START) + P::START
+    }
+}
+
+impl<P: Period> AnimValue for Wrapping<P> {
+    const LANES: usize = 1;
+
+    fn lane(&self, index: usize) -> f32 {
+        assert_eq!(index, 0, "lane index out of bounds");
+        self.0
+    }
+
+    fn lane_mut(&mut self, index: usize) -> &mut f32 {
+        assert_eq!(index, 0, "lane index out of bounds");
+        &mut self.0
+    }
+
+    fn difference(self, other: Self) -> Self {
+        let period = P::END - P::START;
+        let diff = (self.0 - other.0).rem_euclid(period);
+        if diff > period / 2.0 {
+            Self::new(diff - period)
+        } else {
+            Self::new(diff)
+        }
+    }
+}
+
+impl<P: Period> VectorSpace for Wrapping<P> {
+    const ZERO: Self = Self::new(0.0);
+}
+
+impl<P: Period> From<f32> for Wrapping<P> {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<P: Period> Copy for Wrapping<P> {}
+
+impl<P: Period> Clone for Wrapping<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Period> Default for Wrapping<P> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<P: Period> Debug for Wrapping<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Wrapping").field(&self.0).finish()
+    }
+}
+
+impl<P: Period> PartialEq for Wrapping<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P: Period> Add for Wrapping<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.0 + rhs.0)
+    }
+}
+
+impl<P: Period> Sub for Wrapping<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.0 - rhs.0)
+    }
+}
+
+impl<P: Period> Mul<f32> for Wrapping<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.0 * rhs)
+    }
+}
+
+impl<P: Period> Div<f32> for Wrapping<P> {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self {
+        Self::new(self.0 / rhs)
+    }
+}
+
+impl<P: Period> Neg for Wrapping<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.0)
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "simd")]
+
+use bevy_proc_anim::{
+    batch::DynamicsBatch,
+    state::{DynamicsParams, DynamicsState, TickMode},
+};
+
+#[test]
+fn batch_matches_state() {
+    let params = [
+        DynamicsParams::new(1.0, 0.5, 2.0),
+        DynamicsParams::new(2.0, 1.0, 0.0),
+        DynamicsParams::new(0.5, 2.5, -1.0),
+        //slow enough for the first-order shortcut of the analytic tick
+        DynamicsParams::new(0.0005, 0.3, 0.0),
+        //fast enough for the pole matching branch
+        DynamicsParams::new(40.0, 0.2, 0.0),
+    ];
+    //`Simple` diverges for the fast spring, in both
+    for mode in [TickMode::Stable, TickMode::PoleMatching, TickMode::Analytic] {
+        let mut batch = DynamicsBatch::new(mode);
+        let mut states: Vec<_> = params
+            .iter()
+            .map(|&params| {
+                batch.push(0.0, params);
+                DynamicsState::new(0.0, params)
+            })
+            .collect();
+
+        for step in 0..120 {
+            //varying dt, and a target that steps and then ramps
+            let dt = if step % 2 == 0 {
+                1.0 / 60.0
+            } else {
+                1.0 / 30.0
+            };
+            let target = 1.0 + step as f32 * 0.01;
+            batch.targets_mut().fill(target);
+            batch.tick(dt);
+            for (i, state) in states.iter_mut().enumerate() {
+                state.target = target;
+                state.tick(dt, None, mode);
+                let (expected, actual) = (state.value(), batch.value(i));
+                assert!(
+                    (expected - actual).abs() < 1e-3,
+                    "{mode:?} spring {i} at step {step}: expected {expected}, got {actual}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemId, prelude::*, time::TimeUpdateStrategy};
+use bevy_proc_anim::{
+    color::PolarColor,
+    constraint::Limit,
+    filter::{ExponentialSmoothing, MovingAverage, SmoothDamp},
+    pid::PidController,
+    props::TranslationXProperty,
+    snapshot::DynamicsSnapshot,
+    solver::Solver,
+    state::{AnimHandle, DynamicsParams, DynamicsState, TickMode},
+    DynamicsAppExt, DynamicsCommandsExt, DynamicsEntityCommandsExt, DynamicsPlugin,
+};
+
+const MODES: [TickMode; 4] = [
+    TickMode::Simple,
+    TickMode::Stable,
+    TickMode::PoleMatching,
+    TickMode::Analytic,
+];
+
+#[derive(Resource)]
+struct Target(f32);
+
+struct Harness {
+    app: App,
+    handle: AnimHandle<f32>,
+    entity: Entity,
+}
+
+impl Harness {
+    fn new(
+        plugin: DynamicsPlugin,
+        spawn: impl FnOnce(&mut Commands, SystemId<(), f32>) -> AnimHandle<f32>,
+    ) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin))
+            .insert_resource(Target(0.0));
+
+        let world = app.world_mut();
+        let source = world.register_system(|target: Res<Target>| target.0);
+        let mut commands = world.commands();
+        let handle = spawn(&mut commands, source);
+        let entity = commands
+            .spawn(Transform::default())
+            .animate::<TranslationXProperty>(handle)
+            .id();
+        world.flush();
+
+        //the first update always has a zero delta
+        app.update();
+        Self {
+            app,
+            handle,
+            entity,
+        }
+    }
+
+    fn with_mode(params: DynamicsParams, mode: TickMode) -> Self {
+        Self::new(DynamicsPlugin::default(), |commands, source| {
+            let handle = commands.animate_value(0.0, params, source);
+            commands.set_tick_mode(handle, mode);
+            handle
+        })
+    }
+
+    fn with_solver<S: Solver<f32>>(params: S::Params) -> Self {
+        let mut harness = Self::new(DynamicsPlugin::default(), |commands, source| {
+            commands.animate_with_solver::<f32, S>(0.0, params, source)
+        });
+        //the first update skipped the dynamics, so the solver isn't late
+        harness.app.init_solver::<f32, S>();
+        harness
+    }
+
+    fn set_target(&mut self, target: f32) {
+        self.app.world_mut().resource_mut::<Target>().0 = target;
+    }
+
+    fn step(&mut self, dt: f32, steps: usize) {
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                dt,
+            )));
+        for _ in 0..steps {
+            self.app.update();
+        }
+    }
+
+    fn value(&self) -> f32 {
+        self.app
+            .world()
+            .get::<Transform>(self.entity)
+            .unwrap()
+            .translation
+            .x
+    }
+}
+
+#[test]
+fn converges_to_target() {
+    for mode in MODES {
+        let mut harness = Harness::with_mode(DynamicsParams::new(2.0, 0.5, 2.0), mode);
+        harness.set_target(3.0);
+        harness.step(1.0 / 60.0, 300);
+        assert!(
+            (harness.value() - 3.0).abs() < 1e-3,
+            "{mode:?} ended at {}",
+            harness.value()
+        );
+    }
+}
+
+#[test]
+fn follows_moving_target() {
+    for mode in MODES {
+        let mut harness = Harness::with_mode(DynamicsParams::new(2.0, 1.0, 0.0), mode);
+        for i in 1..=300 {
+            harness.set_target(i as f32 / 60.0);
+            harness.step(1.0 / 60.0, 1);
+        }
+        //a critically damped system without response lags a ramp by `k1 = 2z / w`
+        let lag = 1.0 / (2.0 * std::f32::consts::PI);
+        assert!(
+            (harness.value() - (5.0 - lag)).abs() < 0.02,
+            "{mode:?} ended at {}",
+            harness.value()
+        );
+    }
+}
+
+#[test]
+fn stable_at_large_dt() {
+    for mode in [TickMode::Stable, TickMode::PoleMatching, TickMode::Analytic] {
+        let mut harness = Harness::with_mode(DynamicsParams::new(10.0, 0.5, 2.0), mode);
+        harness.set_target(1.0);
+        for _ in 0..50 {
+            harness.step(0.1, 1);
+            assert!(
+                harness.value().abs() < 10.0,
+                "{mode:?} diverged to {}",
+                harness.value()
+            );
+        }
+        assert!(
+            (harness.value() - 1.0).abs() < 1e-3,
+            "{mode:?} ended at {}",
+            harness.value()
+        );
+    }
+
+    //`Simple` has no such guard, so a step this large compared to the period blows up
+    let mut harness = Harness::with_mode(DynamicsParams::new(10.0, 0.5, 2.0), TickMode::Simple);
+    harness.set_target(1.0);
+    harness.step(0.1, 50);
+    let value = harness.value();
+    assert!(
+        value.is_nan() || value.abs() > 10.0,
+        "Simple ended at {value}"
+    );
+}
+
+#[test]
+fn zero_delta_skips_dynamics() {
+    let mut harness = Harness::with_mode(DynamicsParams::new(2.0, 0.5, 2.0), TickMode::Stable);
+    harness.set_target(1.0);
+    harness.step(1.0 / 60.0, 10);
+    let value = harness.value();
+    assert!(value > 0.0);
+
+    harness.set_target(-1.0);
+    harness.step(0.0, 10);
+    assert_eq!(harness.value(), value);
+
+    harness.step(1.0 / 60.0, 1);
+    assert_ne!(harness.value(), value);
+}
+
+#[test]
+fn solvers_converge() {
+    let mut harnesses = [
+        Harness::with_solver::<ExponentialSmoothing>(ExponentialSmoothing::new(0.1)),
+        Harness::with_solver::<SmoothDamp>(SmoothDamp::new(0.3)),
+        Harness::with_solver::<PidController>(PidController::new(40.0, 5.0, 12.0)),
+    ];
+    for harness in &mut harnesses {
+        harness.set_target(2.0);
+        harness.step(1.0 / 60.0, 600);
+        assert!(
+            (harness.value() - 2.0).abs() < 1e-2,
+            "ended at {}",
+            harness.value()
+        );
+    }
+}
+
+#[test]
+fn fixed_timestep_ignores_frame_rate() {
+    let run = |frame_dt: f32, frames: usize| {
+        let mut harness = Harness::new(DynamicsPlugin::fixed_timestep(), |commands, source| {
+            commands.animate_value(0.0, DynamicsParams::new(2.0, 0.5, 2.0), source)
+        });
+        harness.set_target(1.0);
+        harness.step(frame_dt, frames);
+        harness.value()
+    };
+
+    //both run 64 fixed steps of the default 1/64 s
+    let value = run(1.0 / 64.0, 64);
+    assert_eq!(value.to_bits(), run(1.0 / 16.0, 16).to_bits());
+    assert!((value - 1.0).abs() < 0.1);
+}
+
+#[test]
+fn snapshot_rolls_back() {
+    let mut harnesses = [
+        Harness::with_mode(DynamicsParams::new(2.0, 0.5, 2.0), TickMode::Stable),
+        Harness::with_solver::<PidController>(PidController::new(40.0, 5.0, 12.0)),
+    ];
+    for harness in &mut harnesses {
+        harness.set_target(1.0);
+        harness.step(1.0 / 60.0, 10);
+        let snapshot = DynamicsSnapshot::take(harness.app.world_mut());
+
+        harness.step(1.0 / 60.0, 20);
+        let value = harness.value();
+
+        snapshot.restore(harness.app.world_mut());
+        harness.step(1.0 / 60.0, 20);
+        assert_eq!(harness.value().to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn snap_resets_solver() {
+    let mut harness = Harness::with_solver::<MovingAverage>(MovingAverage::new(0.5));
+    harness.set_target(1.0);
+    harness.step(1.0 / 60.0, 60);
+
+    harness.set_target(0.0);
+    let handle = harness.handle;
+    let world = harness.app.world_mut();
+    world.commands().snap_to(handle, 0.0);
+    world.flush();
+    harness.step(1.0 / 60.0, 1);
+    assert_eq!(harness.value(), 0.0);
+}
+
+#[test]
+fn mismatched_axis_params_fall_back_to_params() {
+    let params = DynamicsParams::new(2.0, 0.5, 0.0);
+    let settle = |state: &mut DynamicsState<Vec2>| {
+        state.target = Vec2::ONE;
+        for _ in 0..60 {
+            state.tick(1.0 / 60.0, None, TickMode::Stable);
+        }
+        state.value()
+    };
+    let expected = settle(&mut DynamicsState::new(Vec2::ZERO, params));
+
+    for len in [1, 3] {
+        let mut state = DynamicsState::new(Vec2::ZERO, params);
+        state.set_axis_params(Some(vec![params; len]));
+        let axis_params = state
+            .path_mut::<Option<Vec<DynamicsParams>>>("axis_params")
+            .unwrap();
+        assert!(axis_params.is_none());
+
+        //as a scene or snapshot would, bypassing the check of `set_axis_params`
+        *axis_params = Some(vec![params; len]);
+        assert_eq!(settle(&mut state), expected);
+    }
+}
+
+#[test]
+fn polar_colors_go_around_the_wheel() {
+    let mut state = DynamicsState::new(
+        PolarColor::new(0.5, 0.8, 300.0, 1.0),
+        DynamicsParams::new(1.0, 1.0, 0.0),
+    );
+    state.target = PolarColor::new(0.5, 0.8, 60.0, 1.0);
+    for _ in 0..120 {
+        state.tick(1.0 / 60.0, None, TickMode::Stable);
+        let hue = state.value().hue();
+        assert!(
+            hue >= 300.0 || hue <= 60.0,
+            "went the long way through {hue}"
+        );
+        assert!((state.value().colorfulness - 0.8).abs() < 1e-5);
+    }
+    assert!((state.value().hue() - 60.0).abs() < 0.5);
+}
+
+#[test]
+fn empty_moving_average_follows_target() {
+    for window in [0.0, -1.0, f32::NAN] {
+        let mut harness = Harness::with_solver::<MovingAverage>(MovingAverage { window });
+        harness.set_target(1.0);
+        harness.step(1.0 / 60.0, 1);
+        assert_eq!(harness.value(), 1.0, "window {window}");
+    }
+}
+
+#[test]
+fn norm_limits_never_flip_or_poison() {
+    let mut state = DynamicsState::new(Vec2::ZERO, DynamicsParams::new(2.0, 0.5, 0.0));
+    state.set_state(Vec2::ZERO, Vec2::X);
+    state.constraints.max_speed = Some(Limit::Norm(-1.0));
+    state.tick(1.0 / 60.0, None, TickMode::Stable);
+    assert_eq!(state.velocity(), Vec2::ZERO);
+
+    //a NaN limit is ignored, so the state ticks as if it had none
+    let mut unlimited = state.clone();
+    unlimited.constraints.max_speed = None;
+    state.constraints.max_speed = Some(Limit::Norm(f32::NAN));
+    state.target = Vec2::ONE;
+    unlimited.target = Vec2::ONE;
+    state.tick(1.0 / 60.0, None, TickMode::Stable);
+    unlimited.tick(1.0 / 60.0, None, TickMode::Stable);
+    assert_eq!(state.value(), unlimited.value());
+    assert_eq!(state.velocity(), unlimited.velocity());
+}
+
+#[test]
+#[should_panic(expected = "`DynamicsPlugin` must be added")]
+fn initializing_before_the_plugin_panics() {
+    App::new().init_solver::<f32, PidController>();
+}
@@ -0,0 +1,288 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{entity::EntityHashMap, reflect::AppTypeRegistry, system::SystemId},
+    prelude::*,
+    scene::{ron, serde::SceneDeserializer},
+    time::TimeUpdateStrategy,
+};
+use bevy_animation::animation_curves::AnimatableProperty;
+use bevy_color::{Color, Hsla};
+use bevy_proc_anim::{
+    color::{to_space, ColorField, ColorProperty, PolarColor},
+    path::{PathBindingError, PathDynamics},
+    pid::PidController,
+    prop,
+    props::{TranslationXProperty, TranslationYProperty},
+    state::{AnimHandle, DynamicsParams, TickMode},
+    DynamicsAppExt, DynamicsCommandsExt, DynamicsEntityCommandsExt, DynamicsPlugin,
+};
+use serde::de::DeserializeSeed;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct Health(f32);
+
+prop!(struct HealthProperty, Health, f32, |health| &mut health.0);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Tint(Color);
+
+#[derive(TypePath)]
+struct TintField;
+
+impl ColorField for TintField {
+    type Component = Tint;
+
+    fn color_mut(tint: &mut Tint) -> &mut Color {
+        &mut tint.0
+    }
+}
+
+fn app() -> (App, SystemId<(), f32>) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, DynamicsPlugin::default()))
+        .init_solver::<f32, PidController>()
+        .register_type::<Transform>()
+        .register_type::<Health>()
+        .register_type::<Tint>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )));
+    let source = app.world_mut().register_system(|| 1.0);
+    //the first update has a zero delta, which skips the dynamics
+    app.update();
+    (app, source)
+}
+
+fn save(world: &World, entities: &[Entity]) -> String {
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(entities.iter().copied())
+        .build();
+    scene
+        .serialize(&world.resource::<AppTypeRegistry>().read())
+        .unwrap()
+}
+
+fn load(world: &mut World, save: &str) -> EntityHashMap<Entity> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let mut deserializer = ron::Deserializer::from_str(save).unwrap();
+    let scene = SceneDeserializer {
+        type_registry: &registry.read(),
+    }
+    .deserialize(&mut deserializer)
+    .unwrap();
+
+    let mut entities = EntityHashMap::default();
+    scene.write_to_world(world, &mut entities).unwrap();
+    entities
+}
+
+#[test]
+fn saved_animation_resumes_after_loading() {
+    let (mut original, source) = app();
+    let world = original.world_mut();
+    let mut commands = world.commands();
+    let spring = commands.animate_value(0.0, DynamicsParams::new(2.0, 0.5, 2.0), source);
+    commands.set_tick_mode(spring, TickMode::Stable);
+    let pid = commands.animate_with_solver::<f32, PidController>(
+        0.0,
+        PidController::new(40.0, 5.0, 12.0),
+        source,
+    );
+    let entity = commands
+        .spawn(Transform::default())
+        .animate::<TranslationXProperty>(spring)
+        .animate::<TranslationYProperty>(pid)
+        .animate_path("Transform.translation.z", spring)
+        .id();
+    world.flush();
+
+    for _ in 0..10 {
+        original.update();
+    }
+    let save = save(original.world(), &[spring.entity(), pid.entity(), entity]);
+    for _ in 0..20 {
+        original.update();
+    }
+    let expected = original
+        .world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation;
+
+    let (mut loaded, source) = app();
+    let entities = load(loaded.world_mut(), &save);
+    let mut commands = loaded.world_mut().commands();
+    for handle in [spring, pid] {
+        let handle = AnimHandle::<f32>::from_entity(entities[&handle.entity()]);
+        commands.set_source(handle, source);
+    }
+    loaded.world_mut().flush();
+
+    for _ in 0..20 {
+        loaded.update();
+    }
+    let translation = loaded
+        .world()
+        .get::<Transform>(entities[&entity])
+        .unwrap()
+        .translation;
+    assert_eq!(translation, expected);
+    assert_ne!(translation.x, translation.y);
+    assert_eq!(translation.x, translation.z);
+}
+
+#[test]
+fn path_bindings_are_type_checked() {
+    let (mut app, source) = app();
+    let world = app.world_mut();
+    let mut commands = world.commands();
+    let handle = commands.animate_value(0.0, DynamicsParams::new(2.0, 0.5, 2.0), source);
+    let entity = commands
+        .spawn(Transform::default())
+        .animate::<TranslationXProperty>(handle)
+        .animate_path("Transform.translation.y", handle)
+        .animate_path(
+            "bevy_transform::components::transform::Transform.scale.x",
+            handle,
+        )
+        .animate_path("Transform.translation", handle)
+        .animate_path("Transform.rotation", handle)
+        .animate_path("Transform.position", handle)
+        .animate_path("Player.health", handle)
+        .id();
+    world.flush();
+
+    for _ in 0..10 {
+        app.update();
+    }
+    let transform = app.world().get::<Transform>(entity).unwrap();
+    assert!(transform.translation.x > 0.0);
+    assert_eq!(transform.translation.y, transform.translation.x);
+    assert_eq!(transform.scale.x, transform.translation.x);
+    assert_eq!(transform.rotation, Quat::IDENTITY);
+
+    let errors: Vec<_> = app
+        .world()
+        .get::<PathDynamics>(entity)
+        .unwrap()
+        .bindings()
+        .iter()
+        .map(|binding| binding.error().cloned())
+        .collect();
+    assert!(matches!(
+        errors.as_slice(),
+        [
+            None,
+            None,
+            Some(PathBindingError::TypeMismatch(_)),
+            Some(PathBindingError::NotAnimatable(_)),
+            Some(PathBindingError::InvalidPath(_)),
+            Some(PathBindingError::UnknownComponent(_)),
+        ]
+    ));
+}
+
+#[test]
+fn custom_properties_resolve_once_registered() {
+    let (mut original, source) = app();
+    let world = original.world_mut();
+    let mut commands = world.commands();
+    let handle = commands.animate_value(0.0, DynamicsParams::new(2.0, 0.5, 2.0), source);
+    let entity = commands
+        .spawn(Health::default())
+        .animate::<HealthProperty>(handle)
+        .id();
+    world.flush();
+    let save = save(original.world(), &[handle.entity(), entity]);
+
+    let (mut loaded, source) = app();
+    let entities = load(loaded.world_mut(), &save);
+    let mut commands = loaded.world_mut().commands();
+    commands.set_source(
+        AnimHandle::<f32>::from_entity(entities[&handle.entity()]),
+        source,
+    );
+    loaded.world_mut().flush();
+    let health = |app: &App| app.world().get::<Health>(entities[&entity]).unwrap().0;
+
+    //bound in the original app, but not registered in this one
+    for _ in 0..10 {
+        loaded.update();
+    }
+    assert_eq!(health(&loaded), 0.0);
+
+    loaded.register_property::<HealthProperty>();
+    for _ in 0..10 {
+        loaded.update();
+    }
+    assert!(health(&loaded) > 0.0);
+}
+
+#[test]
+fn color_fields_resolve_in_every_color_space() {
+    let (mut original, _) = app();
+    let world = original.world_mut();
+    let source = world.register_system(|| PolarColor::new(0.5, 0.8, 120.0, 1.0));
+    let mut commands = world.commands();
+    let initial = to_space::<Hsla>(Color::hsl(0.0, 0.8, 0.5));
+    let handle = commands.animate_value(initial, DynamicsParams::new(2.0, 1.0, 0.0), source);
+    let entity = commands
+        .spawn(Tint(Color::hsl(0.0, 0.8, 0.5)))
+        .animate_computed::<ColorProperty<TintField, Hsla>>(handle)
+        .id();
+    world.flush();
+    let save = save(original.world(), &[handle.entity(), entity]);
+
+    let (mut loaded, _) = app();
+    loaded.register_color_field::<TintField>();
+    let entities = load(loaded.world_mut(), &save);
+    let world = loaded.world_mut();
+    let source = world.register_system(|| PolarColor::new(0.5, 0.8, 120.0, 1.0));
+    world
+        .commands()
+        .set_source(AnimHandle::from_entity(entities[&handle.entity()]), source);
+    world.flush();
+
+    for _ in 0..120 {
+        loaded.update();
+    }
+    let tint = loaded.world().get::<Tint>(entities[&entity]).unwrap();
+    assert!((Hsla::from(tint.0).hue - 120.0).abs() < 1.0, "{:?}", tint.0);
+}
+
+#[test]
+fn changed_path_bindings_are_resolved_again() {
+    let (mut app, source) = app();
+    let world = app.world_mut();
+    let mut commands = world.commands();
+    let handle = commands.animate_value(0.0, DynamicsParams::new(2.0, 0.5, 2.0), source);
+    let entity = commands
+        .spawn(Transform::default())
+        .animate_path("Transform.position", handle)
+        .id();
+    world.flush();
+    app.update();
+    let dynamics = app.world().get::<PathDynamics>(entity).unwrap();
+    assert!(matches!(
+        dynamics.bindings()[0].error(),
+        Some(PathBindingError::InvalidPath(_))
+    ));
+
+    //as a scene or an editor would, through reflection
+    *app.world_mut()
+        .get_mut::<PathDynamics>(entity)
+        .unwrap()
+        .path_mut::<String>("bindings[0].path")
+        .unwrap() = "Transform.translation.y".to_owned();
+    for _ in 0..10 {
+        app.update();
+    }
+    let dynamics = app.world().get::<PathDynamics>(entity).unwrap();
+    assert_eq!(dynamics.bindings()[0].error(), None);
+    assert!(app.world().get::<Transform>(entity).unwrap().translation.y > 0.0);
+}
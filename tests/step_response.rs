@@ -0,0 +1,140 @@
+use bevy_proc_anim::{
+    analysis::Response,
+    state::{DynamicsParams, TickMode},
+};
+
+const DT: f32 = 1.0 / 60.0;
+// steps at which the golden curves are sampled
+const SAMPLES: [usize; 5] = [6, 15, 30, 60, 120];
+
+fn assert_curve(params: DynamicsParams, mode: TickMode, golden: [f32; 5]) {
+    let response = params.step_response(mode, DT, 2.0);
+    for (&step, expected) in SAMPLES.iter().zip(golden) {
+        let actual = response.output[step];
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "{params:?} in {mode:?} at step {step}: expected {expected}, got {actual}"
+        );
+    }
+}
+
+#[test]
+fn underdamped_with_response() {
+    let params = DynamicsParams::new(1.0, 0.5, 2.0);
+    let golden = [0.545069, 1.167583, 1.239417, 0.955491, 0.999084];
+    assert_curve(params, TickMode::Simple, golden);
+    assert_curve(params, TickMode::Stable, golden);
+    assert_curve(params, TickMode::PoleMatching, golden);
+    assert_curve(
+        params,
+        TickMode::Analytic,
+        [0.548464, 1.14277, 1.24595, 0.952143, 0.99921],
+    );
+}
+
+#[test]
+fn smooth_damp() {
+    let params = DynamicsParams::smooth_damp(2.0);
+    let golden = [0.349506, 0.804724, 0.976867, 0.999681, 1.0];
+    assert_curve(params, TickMode::Simple, golden);
+    assert_curve(params, TickMode::Stable, golden);
+    assert_curve(params, TickMode::PoleMatching, golden);
+    assert_curve(
+        params,
+        TickMode::Analytic,
+        [0.319842, 0.806114, 0.985095, 0.999948, 1.0],
+    );
+}
+
+#[test]
+fn fast_underdamped() {
+    let params = DynamicsParams::new(3.0, 0.3, 0.0);
+    let golden = [0.908304, 1.103442, 1.04438, 0.998034, 0.999996];
+    assert_curve(params, TickMode::Simple, golden);
+    assert_curve(params, TickMode::Stable, golden);
+    assert_curve(
+        params,
+        TickMode::PoleMatching,
+        [0.859193, 1.167761, 1.041589, 0.999036, 1.000008],
+    );
+    assert_curve(
+        params,
+        TickMode::Analytic,
+        [0.858433, 1.166245, 1.040732, 0.999068, 1.000008],
+    );
+}
+
+#[test]
+fn analytic_barely_depends_on_dt() {
+    let params = DynamicsParams::new(2.0, 0.4, 1.0);
+    let coarse = params.step_response(TickMode::Analytic, 0.1, 2.0);
+    let fine = params.step_response(TickMode::Analytic, 0.01, 2.0);
+    //the target ramps up over the first step, so compare once both have settled past it
+    for (i, coarse) in coarse.output.iter().enumerate().skip(10) {
+        let fine = fine.output[i * 10];
+        assert!((coarse - fine).abs() < 1e-2, "{coarse} and {fine} at {i}");
+    }
+}
+
+#[test]
+fn analytic_keeps_the_damping_of_slow_springs() {
+    //slow enough that `d * dt` takes the first-order shortcut
+    let params = DynamicsParams::new(0.0005, 0.3, 0.0);
+    let analytic = params.step_response(TickMode::Analytic, DT, 1200.0);
+    let stable = params.step_response(TickMode::Stable, DT, 1200.0);
+    for (i, (analytic, stable)) in analytic.output.iter().zip(&stable.output).enumerate() {
+        assert!(
+            (analytic - stable).abs() < 1e-2,
+            "{analytic} and {stable} at {i}"
+        );
+    }
+    assert!(analytic.metrics().overshoot > 30.0);
+}
+
+#[test]
+fn metrics_match_params() {
+    let metrics = DynamicsParams::from_settling_time_and_overshoot(0.5, 0.2)
+        .step_response(TickMode::Analytic, 1.0 / 600.0, 3.0)
+        .metrics();
+    assert!((metrics.overshoot - 20.0).abs() < 0.5, "{metrics:?}");
+    assert!(metrics.settling_time.unwrap() < 0.6, "{metrics:?}");
+    assert!(metrics.steady_state_error.abs() < 1e-3, "{metrics:?}");
+
+    let metrics = DynamicsParams::from_settling_time(0.5)
+        .step_response(TickMode::Analytic, 1.0 / 600.0, 3.0)
+        .metrics();
+    assert_eq!(metrics.overshoot, 0.0);
+    assert!(
+        (metrics.settling_time.unwrap() - 0.5).abs() < 0.01,
+        "{metrics:?}"
+    );
+}
+
+#[test]
+fn zero_step_is_settled() {
+    let response = Response {
+        dt: DT,
+        input: vec![1.0; 10],
+        output: vec![1.0; 10],
+    };
+    let metrics = response.metrics();
+    assert_eq!(metrics.settling_time, Some(0.0));
+    assert_eq!(metrics.overshoot, 0.0);
+    assert_eq!(metrics.steady_state_error, 0.0);
+}
+
+#[test]
+fn impulse_response_isnt_settled_from_the_start() {
+    let metrics = DynamicsParams::new(1.0, 0.5, 0.0)
+        .impulse_response(TickMode::Analytic, DT, 10.0)
+        .metrics();
+    assert!(metrics.overshoot > 0.0, "{metrics:?}");
+    assert_eq!(metrics.rise_time, None);
+    assert!(metrics.settling_time.unwrap() > 1.0, "{metrics:?}");
+}
+
+#[test]
+#[should_panic(expected = "dt must be positive")]
+fn zero_dt_panics() {
+    DynamicsParams::default().step_response(TickMode::Stable, 0.0, 1.0);
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_proc_anim::{
+    props::WorldTranslationProperty, state::DynamicsParams, DynamicsCommandsExt,
+    DynamicsEntityCommandsExt, DynamicsPlugin,
+};
+
+#[test]
+fn world_translation_is_written_in_parent_space() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, DynamicsPlugin::default()))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            1.0 / 60.0,
+        )));
+    let target = Vec3::new(4.0, 2.0, 0.0);
+    let source = app.world_mut().register_system(move || target);
+    //run the schedule before anything is animated, as a game would during loading
+    app.update();
+
+    //no transform propagation runs headless, so the parent's global transform is set up front
+    let world = app.world_mut();
+    let mut commands = world.commands();
+    let handle = commands.animate_value(target, DynamicsParams::new(2.0, 0.5, 2.0), source);
+    let parent = commands
+        .spawn((
+            Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+            GlobalTransform::from(Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(2.0))),
+        ))
+        .id();
+    let child = commands
+        .spawn(Transform::default())
+        .set_parent(parent)
+        .animate_world::<WorldTranslationProperty>(handle)
+        .id();
+    world.flush();
+
+    for _ in 0..3 {
+        app.update();
+    }
+    let translation = app.world().get::<Transform>(child).unwrap().translation;
+    assert_eq!(translation, Vec3::new(1.5, 1.0, 0.0));
+}